@@ -0,0 +1,41 @@
+use aoc_core::Part;
+
+/// Each elf's total calories, in input order.
+pub fn parse(input: &str) -> Vec<u32> {
+    input.lines().fold(vec![0], |mut calories, line| {
+        if line.is_empty() {
+            calories.push(0);
+        }
+        if let (Some(total), Ok(amount)) = (calories.last_mut(), line.parse::<u32>()) {
+            *total += amount
+        }
+        calories
+    })
+}
+
+pub fn top(calories: &[u32], num: usize) -> u32 {
+    let mut calories = calories.to_vec();
+    calories.sort_unstable();
+    calories.iter().rev().take(num).sum()
+}
+
+pub fn part1(calories: &[u32]) -> u32 {
+    top(calories, 1)
+}
+
+pub fn part2(calories: &[u32]) -> u32 {
+    top(calories, 3)
+}
+
+pub fn solve(input: &str) -> (u32, u32) {
+    let calories = parse(input);
+    (part1(&calories), part2(&calories))
+}
+
+pub fn solve_part(input: &str, part: Part) -> String {
+    let calories = parse(input);
+    match part {
+        Part::One => part1(&calories).to_string(),
+        Part::Two => part2(&calories).to_string(),
+    }
+}
@@ -1,24 +1,37 @@
+use aoc_core::{Input, Output, Part};
 use clap::Parser;
-use std::io;
+use day_01_calorie_counting::{parse, solve, solve_part, top};
+use std::convert::Infallible;
 
 #[derive(Parser)]
 struct Args {
-    #[arg(default_value_t = 1)]
-    num: usize,
+    /// Total the calories of the top NUM elves [default: solve both parts]
+    #[arg(long, value_name = "NUM")]
+    num: Option<usize>,
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let Args { num } = Args::parse();
-    let input: Vec<String> = io::stdin().lines().map(Result::unwrap).collect();
-    let mut calories = input.iter().fold(vec![0], |mut calories, line| {
-        if line.is_empty() {
-            calories.push(0);
+    let Args { num, input, output } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(
+        env!("CARGO_MANIFEST_DIR"),
+        &input,
+        &Part::ALL,
+        |input, part| Ok::<_, Infallible>(solve_part(input, part)),
+    ) {
+        return;
+    }
+    match num {
+        Some(num) => println!("{}", top(&parse(&input), num)),
+        None => {
+            let (part1, part2) = solve(&input);
+            aoc_core::print_answers(part1, part2);
         }
-        if let (Some(total), Ok(amount)) = (calories.last_mut(), line.parse::<u32>()) {
-            *total += amount
-        }
-        calories
-    });
-    calories.sort_unstable();
-    println!("{}", calories.iter().rev().take(num).sum::<u32>());
+    }
 }
@@ -0,0 +1,130 @@
+use aoc_core::Part;
+use std::str::FromStr;
+
+#[derive(Copy, Clone)]
+pub enum Move {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+#[derive(Debug)]
+pub struct ParseError;
+
+impl std::str::FromStr for Move {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(Move::Rock),
+            "B" => Ok(Move::Paper),
+            "C" => Ok(Move::Scissors),
+            "X" => Ok(Move::Rock),
+            "Y" => Ok(Move::Paper),
+            "Z" => Ok(Move::Scissors),
+            _ => Err(ParseError),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum Strategy {
+    Lose,
+    Draw,
+    Win,
+}
+
+trait MoveFor {
+    fn move_for(&self, oponent_move: &Move) -> Move;
+}
+
+impl std::str::FromStr for Strategy {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(Strategy::Lose),
+            "Y" => Ok(Strategy::Draw),
+            "Z" => Ok(Strategy::Win),
+            _ => Err(ParseError),
+        }
+    }
+}
+
+impl MoveFor for Strategy {
+    fn move_for(&self, oponent_move: &Move) -> Move {
+        match (oponent_move, self) {
+            (Move::Rock, Strategy::Lose) => Move::Scissors,
+            (Move::Rock, Strategy::Draw) => Move::Rock,
+            (Move::Rock, Strategy::Win) => Move::Paper,
+            (Move::Paper, Strategy::Lose) => Move::Rock,
+            (Move::Paper, Strategy::Draw) => Move::Paper,
+            (Move::Paper, Strategy::Win) => Move::Scissors,
+            (Move::Scissors, Strategy::Lose) => Move::Paper,
+            (Move::Scissors, Strategy::Draw) => Move::Scissors,
+            (Move::Scissors, Strategy::Win) => Move::Rock,
+        }
+    }
+}
+
+/// One line of the guide, with the second column read both ways.
+pub struct Round {
+    opponent: Move,
+    play: Move,
+    strategy: Strategy,
+}
+
+pub fn parse(input: &str) -> Vec<Round> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let (opponent, suggested_play) = line.split_once(' ')?;
+            Some(Round {
+                opponent: Move::from_str(opponent).ok()?,
+                play: Move::from_str(suggested_play).ok()?,
+                strategy: Strategy::from_str(suggested_play).ok()?,
+            })
+        })
+        .collect()
+}
+
+fn score(opponent_move: &Move, suggested_play: &Move) -> u32 {
+    match (opponent_move, suggested_play) {
+        (Move::Rock, Move::Rock) => 4,         // 1 for rock, 3 for draw
+        (Move::Rock, Move::Paper) => 8,        // 2 for paper, 6 for win
+        (Move::Rock, Move::Scissors) => 3,     // 3 for scisors, 0 for loss
+        (Move::Paper, Move::Rock) => 1,        // 1 for rock, 0 for loss
+        (Move::Paper, Move::Paper) => 5,       // 2 for paper, 3 for draw
+        (Move::Paper, Move::Scissors) => 9,    // 3 for scisors, 6 for win
+        (Move::Scissors, Move::Rock) => 7,     // 1 for rock, 6 for win
+        (Move::Scissors, Move::Paper) => 2,    // 2 for paper, 0 for loss
+        (Move::Scissors, Move::Scissors) => 6, // 3 for scisors, 3 for draw
+    }
+}
+
+pub fn part1(rounds: &[Round]) -> u32 {
+    rounds
+        .iter()
+        .map(|round| score(&round.opponent, &round.play))
+        .sum()
+}
+
+pub fn part2(rounds: &[Round]) -> u32 {
+    rounds
+        .iter()
+        .map(|round| score(&round.opponent, &round.strategy.move_for(&round.opponent)))
+        .sum()
+}
+
+pub fn solve(input: &str) -> (u32, u32) {
+    let rounds = parse(input);
+    (part1(&rounds), part2(&rounds))
+}
+
+pub fn solve_part(input: &str, part: Part) -> String {
+    let rounds = parse(input);
+    match part {
+        Part::One => part1(&rounds).to_string(),
+        Part::Two => part2(&rounds).to_string(),
+    }
+}
@@ -0,0 +1,63 @@
+use aoc_core::Part;
+
+fn common<T: AsRef<str>>(strings: &[T]) -> Vec<char> {
+    strings
+        .iter()
+        .enumerate()
+        .fold(vec![], |common, (i, string)| {
+            if i == 0 {
+                string.as_ref().chars().collect()
+            } else {
+                common
+                    .into_iter()
+                    .filter(|char| string.as_ref().contains(*char))
+                    .collect()
+            }
+        })
+}
+
+fn priority(char: char) -> u32 {
+    let ord = char as u32;
+    if char.is_ascii_lowercase() {
+        ord - 96
+    } else if char.is_ascii_uppercase() {
+        ord - 38
+    } else {
+        0
+    }
+}
+
+pub fn parse(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}
+
+pub fn part1(rucksacks: &[&str]) -> u32 {
+    rucksacks.iter().fold(0, |mut sum, line| {
+        let (first, second) = line.split_at(line.len() / 2);
+        if let Some(&item) = common(&[first, second]).first() {
+            sum += priority(item);
+        }
+        sum
+    })
+}
+
+pub fn part2(rucksacks: &[&str]) -> u32 {
+    rucksacks
+        .chunks(3)
+        .map(common)
+        .map(|chars| priority(*chars.first().unwrap()))
+        .sum()
+}
+
+pub fn solve(input: &str) -> (u32, u32) {
+    let rucksacks = parse(input);
+    (part1(&rucksacks), part2(&rucksacks))
+}
+
+pub fn solve_part(input: &str, part: Part) -> String {
+    let rucksacks = parse(input);
+    match part {
+        Part::One => part1(&rucksacks).to_string(),
+        Part::Two => part2(&rucksacks).to_string(),
+    }
+}
@@ -1,56 +1,39 @@
+use aoc_core::{Input, Output, Part};
 use clap::Parser;
-use std::io;
+use day_03_rucksack_reorganization::{solve, solve_part};
+use std::convert::Infallible;
 
 #[derive(Parser)]
 struct Args {
     #[arg(long, default_value_t = false)]
     badges: bool,
-}
-
-fn common<T: AsRef<str>>(strings: &[T]) -> Vec<char> {
-    strings
-        .iter()
-        .enumerate()
-        .fold(vec![], |common, (i, string)| {
-            if i == 0 {
-                string.as_ref().chars().collect()
-            } else {
-                common
-                    .into_iter()
-                    .filter(|char| string.as_ref().contains(*char))
-                    .collect()
-            }
-        })
-}
-
-fn priority(char: char) -> u32 {
-    let ord = char as u32;
-    if char.is_ascii_lowercase() {
-        ord - 96
-    } else if char.is_ascii_uppercase() {
-        ord - 38
-    } else {
-        0
-    }
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let Args { badges } = Args::parse();
-    let input: Vec<String> = io::stdin().lines().map(Result::unwrap).collect();
-    let sum = if badges {
-        input
-            .chunks(3)
-            .map(common)
-            .map(|chars| priority(*chars.first().unwrap()))
-            .sum()
+    let Args {
+        badges,
+        input,
+        output,
+    } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(
+        env!("CARGO_MANIFEST_DIR"),
+        &input,
+        &Part::ALL,
+        |input, part| Ok::<_, Infallible>(solve_part(input, part)),
+    ) {
+        return;
+    }
+    if badges {
+        println!("{}", solve_part(&input, Part::Two));
     } else {
-        input.iter().fold(0, |mut sum, line| {
-            let (first, second) = line.split_at(line.len() / 2);
-            if let Some(&item) = common(&[first, second]).first() {
-                sum += priority(item);
-            }
-            sum
-        })
-    };
-    println!("{}", sum);
+        let (part1, part2) = solve(&input);
+        aoc_core::print_answers(part1, part2);
+    }
 }
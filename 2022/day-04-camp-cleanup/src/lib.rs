@@ -0,0 +1,90 @@
+use aoc_core::{
+    parse::{parse_lines, tag, IResult, ParseError},
+    Part,
+};
+use nom::{character::complete::u32, combinator::map, sequence::separated_pair};
+use std::{fmt, ops::Range};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid assignment pair at {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+trait Compare<Range> {
+    fn contains(&self, other: &Self) -> bool;
+    fn overlaps(&self, other: &Self) -> bool;
+}
+
+impl<T: std::cmp::PartialOrd> Compare<Range<T>> for Range<T> {
+    fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+    fn overlaps(&self, other: &Self) -> bool {
+        self.start < other.end && self.end > other.start
+    }
+}
+
+fn range(input: &str) -> IResult<'_, Range<u32>> {
+    map(separated_pair(u32, tag("-"), u32), |(start, end)| Range {
+        start,
+        end: end + 1,
+    })(input)
+}
+
+fn pair(input: &str) -> IResult<'_, Pair> {
+    separated_pair(range, tag(","), range)(input)
+}
+
+type Pair = (Range<u32>, Range<u32>);
+
+pub fn parse(input: &str) -> Result<Vec<Pair>, Error> {
+    Ok(parse_lines(input, pair)?)
+}
+
+fn count(pairs: &[Pair], overlap: bool) -> usize {
+    pairs
+        .iter()
+        .filter(|(first, second)| {
+            overlap && first.overlaps(second)
+                || Compare::contains(first, second)
+                || Compare::contains(second, first)
+        })
+        .count()
+}
+
+pub fn part1(pairs: &[Pair]) -> usize {
+    count(pairs, false)
+}
+
+pub fn part2(pairs: &[Pair]) -> usize {
+    count(pairs, true)
+}
+
+pub fn solve(input: &str) -> Result<(usize, usize), Error> {
+    let pairs = parse(input)?;
+    Ok((part1(&pairs), part2(&pairs)))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let pairs = parse(input)?;
+    Ok(match part {
+        Part::One => part1(&pairs).to_string(),
+        Part::Two => part2(&pairs).to_string(),
+    })
+}
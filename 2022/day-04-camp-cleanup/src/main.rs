@@ -1,53 +1,34 @@
+use aoc_core::{Input, Output, Part};
 use clap::Parser;
-use itertools::Itertools;
-use std::{io, ops::Range};
+use day_04_camp_cleanup::{solve, solve_part};
 
 #[derive(Parser)]
 struct Args {
     #[arg(long, default_value_t = false)]
     overlap: bool,
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
-trait Compare<Range> {
-    fn contains(&self, other: &Self) -> bool;
-    fn overlaps(&self, other: &Self) -> bool;
-}
-
-impl<T: std::cmp::PartialOrd> Compare<Range<T>> for Range<T> {
-    fn contains(&self, other: &Self) -> bool {
-        self.start <= other.start && self.end >= other.end
+fn main() {
+    let Args {
+        overlap,
+        input,
+        output,
+    } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(env!("CARGO_MANIFEST_DIR"), &input, &Part::ALL, solve_part) {
+        return;
     }
-    fn overlaps(&self, other: &Self) -> bool {
-        self.start < other.end && self.end > other.start
+    if overlap {
+        let answer = solve_part(&input, Part::Two).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", answer);
+    } else {
+        let (part1, part2) = solve(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        aoc_core::print_answers(part1, part2);
     }
 }
-
-fn main() {
-    let Args { overlap } = Args::parse();
-    let input: Vec<String> = io::stdin().lines().map(Result::unwrap).collect();
-    let overlaps = input.iter().fold(0, |mut overlaps, line| {
-        let (first, second) = line
-            .split(',')
-            .map(|range| {
-                range
-                    .split('-')
-                    .map(|num| num.parse::<usize>().unwrap())
-                    .collect_tuple()
-                    .unwrap()
-            })
-            .map(|(start, end)| Range {
-                start,
-                end: end + 1,
-            })
-            .collect_tuple()
-            .unwrap();
-        if overlap && first.overlaps(&second)
-            || Compare::contains(&first, &second)
-            || Compare::contains(&second, &first)
-        {
-            overlaps += 1;
-        }
-        overlaps
-    });
-    println!("{}", overlaps);
-}
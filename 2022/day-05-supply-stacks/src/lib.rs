@@ -0,0 +1,189 @@
+use aoc_core::{
+    parse::{parse_line, tag, IResult, ParseError},
+    Part,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::take_until,
+    character::complete::digit1,
+    combinator::{map, map_res},
+    multi::separated_list1,
+    sequence::{delimited, preceded, tuple},
+};
+use std::{cell::RefCell, collections::BTreeMap, fmt};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    UnknownStack {
+        line: usize,
+        stack: usize,
+    },
+    NotEnoughCrates {
+        line: usize,
+        stack: usize,
+        quantity: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid drawing or procedure at {}", err),
+            Error::UnknownStack { line, stack } => {
+                write!(f, "line {}: there is no stack {}", line, stack)
+            }
+            Error::NotEnoughCrates {
+                line,
+                stack,
+                quantity,
+            } => write!(
+                f,
+                "line {}: stack {} has fewer than {} crates",
+                line, stack, quantity
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Rearrangement {
+    quantity: usize,
+    from: usize,
+    to: usize,
+}
+
+fn crate_layer(input: &str) -> IResult<'_, Vec<&str>> {
+    separated_list1(
+        tag(" "),
+        alt((
+            preceded(tag("   "), tag("")),
+            delimited(tag("["), take_until("]"), tag("]")),
+        )),
+    )(input)
+}
+
+fn num_preceded_by(pre: &'static str) -> impl Fn(&str) -> IResult<'_, usize> {
+    move |i| map_res(preceded(tag(pre), digit1), str::parse::<usize>)(i)
+}
+
+fn procedure(input: &str) -> IResult<'_, Rearrangement> {
+    map(
+        tuple((
+            num_preceded_by("move "),
+            num_preceded_by(" from "),
+            num_preceded_by(" to "),
+        )),
+        |(quantity, from, to)| Rearrangement { quantity, from, to },
+    )(input)
+}
+
+#[derive(Debug)]
+pub struct Supplies<'a> {
+    stacks: BTreeMap<usize, Vec<&'a str>>,
+    /// Each rearrangement with the line it was read from.
+    rearrangements: Vec<(usize, Rearrangement)>,
+}
+
+pub fn parse(input: &str) -> Result<Supplies<'_>, Error> {
+    let mut stacks: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    let mut rearrangements = Vec::new();
+    let (drawing, procedures) = input.split_once("\n\n").unwrap_or((input, ""));
+    // The last line of the drawing only numbers the stacks.
+    let height = drawing.lines().count();
+
+    for (i, line) in drawing.lines().take(height.saturating_sub(1)).enumerate() {
+        parse_line(i, line, crate_layer)?
+            .iter()
+            .enumerate()
+            .for_each(|(i, &supply)| {
+                if !supply.is_empty() {
+                    stacks.entry(i + 1).or_default().push(supply);
+                }
+            });
+    }
+
+    stacks.values_mut().for_each(|stack| stack.reverse());
+
+    for (i, line) in procedures.lines().enumerate() {
+        let i = height + 1 + i;
+        let rearrangement = parse_line(i, line, procedure)?;
+        for stack in [rearrangement.from, rearrangement.to] {
+            if !stacks.contains_key(&stack) {
+                return Err(Error::UnknownStack { line: i + 1, stack });
+            }
+        }
+        rearrangements.push((i + 1, rearrangement));
+    }
+
+    Ok(Supplies {
+        stacks,
+        rearrangements,
+    })
+}
+
+fn rearrange(supplies: &Supplies, multi: bool) -> Result<String, Error> {
+    let stacks: BTreeMap<usize, RefCell<Vec<&str>>> = supplies
+        .stacks
+        .iter()
+        .map(|(&i, stack)| (i, RefCell::new(stack.clone())))
+        .collect();
+
+    for &(line, Rearrangement { quantity, from, to }) in &supplies.rearrangements {
+        let (source, target) = (&stacks[&from], &stacks[&to]);
+        if source.borrow().len() < quantity {
+            return Err(Error::NotEnoughCrates {
+                line,
+                stack: from,
+                quantity,
+            });
+        }
+        if from == to {
+            continue;
+        }
+        let mut from = source.borrow_mut();
+        let mut to = target.borrow_mut();
+        if multi {
+            let len = from.len();
+            to.extend(from.split_off(len - quantity));
+        } else {
+            for _ in 0..quantity {
+                to.push(from.pop().unwrap());
+            }
+        }
+    }
+
+    Ok(stacks
+        .values()
+        .map(|stack| stack.borrow().last().copied().unwrap_or(" "))
+        .collect::<String>())
+}
+
+pub fn part1(supplies: &Supplies) -> Result<String, Error> {
+    rearrange(supplies, false)
+}
+
+pub fn part2(supplies: &Supplies) -> Result<String, Error> {
+    rearrange(supplies, true)
+}
+
+pub fn solve(input: &str) -> Result<(String, String), Error> {
+    let supplies = parse(input)?;
+    Ok((part1(&supplies)?, part2(&supplies)?))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let supplies = parse(input)?;
+    match part {
+        Part::One => part1(&supplies),
+        Part::Two => part2(&supplies),
+    }
+}
@@ -0,0 +1,62 @@
+use aoc_core::Part;
+use std::{collections::HashSet, fmt};
+
+#[derive(Debug)]
+pub enum Error {
+    NoMarker { length: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NoMarker { length } => {
+                write!(
+                    f,
+                    "no run of {} distinct characters in the datastream",
+                    length
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn find_first_unique(input: &str, length: usize) -> Option<usize> {
+    input
+        .chars()
+        .collect::<Vec<char>>()
+        .windows(length)
+        .position(|w| HashSet::<_>::from_iter(w).len() == length)
+        .map(|position| position + length)
+}
+
+pub fn parse(input: &str) -> &str {
+    input.lines().next().unwrap_or("")
+}
+
+fn marker(datastream: &str, length: usize) -> Result<usize, Error> {
+    find_first_unique(datastream, length).ok_or(Error::NoMarker { length })
+}
+
+pub fn part1(datastream: &str) -> Result<usize, Error> {
+    marker(datastream, 4)
+}
+
+pub fn part2(datastream: &str) -> Result<usize, Error> {
+    marker(datastream, 14)
+}
+
+pub fn solve(input: &str) -> Result<(usize, usize), Error> {
+    let datastream = parse(input);
+    Ok((part1(datastream)?, part2(datastream)?))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let datastream = parse(input);
+    match part {
+        Part::One => part1(datastream),
+        Part::Two => part2(datastream),
+    }
+    .map(|position| position.to_string())
+}
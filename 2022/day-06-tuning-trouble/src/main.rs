@@ -1,27 +1,34 @@
+use aoc_core::{Input, Output, Part};
 use clap::Parser;
-use std::{collections::HashSet, io};
+use day_06_tuning_trouble::{solve, solve_part};
 
 #[derive(Parser)]
 struct Args {
     #[arg(long, default_value_t = false)]
     message: bool,
-}
-
-fn find_first_unique(input: &str, length: usize) -> usize {
-    input
-        .chars()
-        .collect::<Vec<char>>()
-        .windows(length)
-        .position(|w| HashSet::<_>::from_iter(w).len() == length)
-        .unwrap()
-        + length
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let Args { message } = Args::parse();
-    let input = io::stdin().lines().next().unwrap().unwrap();
-    let length = if message { 14 } else { 4 };
-    let offset = find_first_unique(&input, length);
-
-    println!("{:?}", offset);
+    let Args {
+        message,
+        input,
+        output,
+    } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(env!("CARGO_MANIFEST_DIR"), &input, &Part::ALL, solve_part) {
+        return;
+    }
+    if message {
+        let answer = solve_part(&input, Part::Two).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", answer);
+    } else {
+        let (part1, part2) = solve(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        aoc_core::print_answers(part1, part2);
+    }
 }
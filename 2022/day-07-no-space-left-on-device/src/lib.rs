@@ -0,0 +1,262 @@
+use aoc_core::{
+    parse::{parse_lines, tag, IResult, ParseError},
+    Part,
+};
+use nom::{
+    branch::alt,
+    character::complete::digit1,
+    combinator::{map, map_res, rest},
+    sequence::{preceded, terminated},
+};
+use std::fmt;
+
+const DISK: usize = 70000000;
+const NEEDED: usize = 30000000;
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    UnknownDirectory { line: usize, name: String },
+    Overfull { used: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid terminal output at {}", err),
+            Error::UnknownDirectory { line, name } => write!(
+                f,
+                "line {}: cannot cd into {:?}, which has not been listed",
+                line, name
+            ),
+            Error::Overfull { used } => write!(
+                f,
+                "the files take up {} but the disk only holds {}",
+                used, DISK
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+#[derive(Debug)]
+enum Command<'a> {
+    ChangeDirectory { name: &'a str },
+    List,
+}
+
+#[derive(Debug)]
+enum Line<'a> {
+    Command(Command<'a>),
+    Directory(Directory<'a>),
+    File(usize),
+}
+
+#[derive(Debug)]
+pub struct Directory<'a> {
+    name: &'a str,
+    directories: Vec<usize>,
+    files: Vec<usize>,
+    parent: usize,
+}
+
+impl<'a> Directory<'a> {
+    fn new(name: &'a str) -> Self {
+        Directory {
+            name,
+            directories: Vec::new(),
+            files: Vec::new(),
+            parent: 0,
+        }
+    }
+}
+
+fn command(input: &str) -> IResult<'_, Command<'_>> {
+    preceded(
+        tag("$ "),
+        alt((
+            map(tag("ls"), |_| Command::List),
+            map(preceded(tag("cd "), rest), |name| {
+                Command::ChangeDirectory { name }
+            }),
+        )),
+    )(input)
+}
+
+fn directory(input: &str) -> IResult<'_, Directory<'_>> {
+    map(preceded(tag("dir "), rest), Directory::new)(input)
+}
+
+fn file(input: &str) -> IResult<'_, usize> {
+    map_res(terminated(digit1, preceded(tag(" "), rest)), str::parse)(input)
+}
+
+fn line(input: &str) -> IResult<'_, Line<'_>> {
+    alt((
+        map(command, Line::Command),
+        map(directory, Line::Directory),
+        map(file, Line::File),
+    ))(input)
+}
+
+/// The directory tree described by the terminal output, with `/` at index 0.
+pub fn parse(input: &str) -> Result<Vec<Directory<'_>>, Error> {
+    let mut directories = vec![Directory::new("/")];
+    let mut current_idx: usize = 0;
+
+    for (i, line) in parse_lines(input, line)?.into_iter().enumerate() {
+        match line {
+            Line::Command(Command::ChangeDirectory { name }) => {
+                let dir = &directories[current_idx];
+                current_idx = match name {
+                    "/" => 0,
+                    ".." => dir.parent,
+                    _ => *dir
+                        .directories
+                        .iter()
+                        .find(|&&idx| directories[idx].name == name)
+                        .ok_or_else(|| Error::UnknownDirectory {
+                            line: i + 1,
+                            name: name.to_string(),
+                        })?,
+                };
+            }
+            Line::Command(Command::List) => {}
+            Line::Directory(mut directory) => {
+                directory.parent = current_idx;
+                let idx = directories.len();
+                directories.push(directory);
+                if let Some(dir) = directories.get_mut(current_idx) {
+                    dir.directories.push(idx);
+                }
+            }
+            Line::File(file) => {
+                if let Some(dir) = directories.get_mut(current_idx) {
+                    dir.files.push(file);
+                }
+            }
+        }
+    }
+
+    Ok(directories)
+}
+
+/// The total size of the directory at `idx`, including everything below it.
+pub fn size(directories: &[Directory], idx: usize) -> usize {
+    directories
+        .get(idx)
+        .map(|dir| {
+            dir.files.iter().sum::<usize>()
+                + dir
+                    .directories
+                    .iter()
+                    .fold(0, |total, idx| total + size(directories, *idx))
+        })
+        .unwrap_or(0)
+}
+
+/// The total size of every directory that satisfies `f`.
+pub fn filter_by_size<'a, F: FnMut(&usize) -> bool + 'a>(
+    directories: &'a [Directory<'a>],
+    f: F,
+) -> impl Iterator<Item = usize> + 'a {
+    (0..directories.len())
+        .map(move |idx| size(directories, idx))
+        .filter(f)
+}
+
+pub fn part1(directories: &[Directory]) -> usize {
+    filter_by_size(directories, |size: &usize| *size <= 100000).sum()
+}
+
+pub fn part2(directories: &[Directory]) -> Result<usize, Error> {
+    let used = size(directories, 0);
+    let free = DISK.checked_sub(used).ok_or(Error::Overfull { used })?;
+    let needed = NEEDED.saturating_sub(free);
+    // Deleting `/` always frees enough, so some directory always qualifies.
+    Ok(filter_by_size(directories, |size: &usize| *size >= needed)
+        .min()
+        .unwrap_or(used))
+}
+
+pub fn solve(input: &str) -> Result<(usize, usize), Error> {
+    let directories = parse(input)?;
+    Ok((part1(&directories), part2(&directories)?))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let directories = parse(input)?;
+    Ok(match part {
+        Part::One => part1(&directories).to_string(),
+        Part::Two => part2(&directories)?.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, part1, part2, size, Error};
+
+    const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn sizes_nest() {
+        let directories = parse(EXAMPLE).unwrap();
+        let sizes: Vec<usize> = (0..directories.len())
+            .map(|idx| size(&directories, idx))
+            .collect();
+        assert_eq!(sizes, vec![48381165, 94853, 24933642, 584]);
+    }
+
+    #[test]
+    fn solves_example() {
+        let directories = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&directories), 95437);
+        assert_eq!(part2(&directories).unwrap(), 24933642);
+    }
+
+    #[test]
+    fn rejects_impossible_output() {
+        assert!(matches!(
+            parse("$ cd /\n$ ls\ndir a\n$ cd b"),
+            Err(Error::UnknownDirectory { line: 4, .. })
+        ));
+        let full = parse("$ cd /\n$ ls\n80000000 big").unwrap();
+        assert!(matches!(
+            part2(&full),
+            Err(Error::Overfull { used: 80000000 })
+        ));
+        // With room to spare, the smallest directory will do.
+        let roomy = parse("$ cd /\n$ ls\ndir a\n5 b\n$ cd a\n$ ls\n3 c").unwrap();
+        assert_eq!(part2(&roomy).unwrap(), 3);
+    }
+}
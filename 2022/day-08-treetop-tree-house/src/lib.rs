@@ -0,0 +1,71 @@
+use aoc_core::Part;
+use aoc_grid::{Direction, Grid, ParseGridError};
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Map(ParseGridError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Map(ParseGridError::InvalidCell { x, y, c }) => write!(
+                f,
+                "invalid tree map at line {}, column {}: expected a digit, found {:?}",
+                y + 1,
+                x + 1,
+                c
+            ),
+            Error::Map(err) => write!(f, "invalid tree map at {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub fn parse(input: &str) -> Result<Grid<u32>, Error> {
+    Grid::parse_with(input, |c| c.to_digit(10).ok_or(())).map_err(Error::Map)
+}
+
+pub fn part1(trees: &Grid<u32>) -> usize {
+    trees
+        .iter()
+        .filter(|&(p, &height)| {
+            Direction::CARDINAL
+                .iter()
+                .any(|&d| trees.ray(p, d).all(|q| trees[q] < height))
+        })
+        .count()
+}
+
+pub fn part2(trees: &Grid<u32>) -> usize {
+    trees
+        .iter()
+        .map(|(p, &height)| {
+            Direction::CARDINAL
+                .iter()
+                .map(|&d| {
+                    trees
+                        .ray(p, d)
+                        .position(|q| trees[q] >= height)
+                        .map_or_else(|| trees.ray(p, d).count(), |i| i + 1)
+                })
+                .product()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn solve(input: &str) -> Result<(usize, usize), Error> {
+    let trees = parse(input)?;
+    Ok((part1(&trees), part2(&trees)))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let trees = parse(input)?;
+    Ok(match part {
+        Part::One => part1(&trees).to_string(),
+        Part::Two => part2(&trees).to_string(),
+    })
+}
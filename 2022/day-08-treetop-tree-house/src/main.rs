@@ -1,72 +1,34 @@
+use aoc_core::{Input, Output, Part};
 use clap::Parser;
-use std::io;
+use day_08_treetop_tree_house::{solve, solve_part};
 
 #[derive(Parser)]
 struct Args {
     #[arg(long, default_value_t = false)]
     score: bool,
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let Args { score } = Args::parse();
-    let input: Vec<Vec<u32>> = io::stdin()
-        .lines()
-        .map(|line| line.unwrap().chars().flat_map(|c| c.to_digit(10)).collect())
-        .collect();
-    let width = input.iter().map(|row| row.len()).max().unwrap();
-    let depth = input.len();
-
-    let result: usize = if score {
-        let last_row = depth - 1;
-        let last_col = width - 1;
-        (1..last_row)
-            .flat_map(|y| {
-                (1..last_col)
-                    .map(|x| {
-                        let height = input[y][x];
-                        let blocks_view = |x: usize, y: usize| input[y][x] >= height;
-                        let plus_one = |x: usize| x + 1;
-
-                        (0..x)
-                            .rev()
-                            .position(|x| blocks_view(x, y))
-                            .map_or(x, plus_one)
-                            * (x + 1..width)
-                                .position(|x| blocks_view(x, y))
-                                .map_or(last_col - x, plus_one)
-                            * (0..y)
-                                .rev()
-                                .position(|y| blocks_view(x, y))
-                                .map_or(y, plus_one)
-                            * (y + 1..depth)
-                                .position(|y| blocks_view(x, y))
-                                .map_or(last_row - y, plus_one)
-                    })
-                    .collect::<Vec<usize>>()
-            })
-            .max()
-            .unwrap()
+    let Args {
+        score,
+        input,
+        output,
+    } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(env!("CARGO_MANIFEST_DIR"), &input, &Part::ALL, solve_part) {
+        return;
+    }
+    if score {
+        let answer = solve_part(&input, Part::Two).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", answer);
     } else {
-        (0..depth)
-            .map(|y| {
-                (0..width)
-                    .filter(|&x| {
-                        if x == 0 || x == width - 1 || y == 0 || y == depth - 1 {
-                            true
-                        } else {
-                            let height = input[y][x];
-                            let is_visible = |x: usize, y: usize| input[y][x] < height;
-
-                            (0..x).all(|x| is_visible(x, y))
-                                || (x + 1..width).all(|x| is_visible(x, y))
-                                || (0..y).all(|y| is_visible(x, y))
-                                || (y + 1..depth).all(|y| is_visible(x, y))
-                        }
-                    })
-                    .count()
-            })
-            .sum()
-    };
-
-    println!("{}", result)
+        let (part1, part2) = solve(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        aoc_core::print_answers(part1, part2);
+    }
 }
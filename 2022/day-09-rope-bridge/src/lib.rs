@@ -0,0 +1,98 @@
+use aoc_core::parse::{parse_lines, tag, IResult, ParseError};
+use aoc_core::Part;
+use aoc_point::Point2;
+use lending_iterator::{lending_iterator::constructors::windows_mut, LendingIterator};
+use nom::{branch::alt, character::complete::u32, combinator::map, sequence::preceded};
+use std::{collections::HashSet, fmt};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid motion at {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+type Point = Point2<i32>;
+
+#[derive(Debug)]
+pub enum Motion {
+    Up(u32),
+    Down(u32),
+    Left(u32),
+    Right(u32),
+}
+
+fn motion(input: &str) -> IResult<'_, Motion> {
+    alt((
+        map(preceded(tag("U "), u32), Motion::Up),
+        map(preceded(tag("D "), u32), Motion::Down),
+        map(preceded(tag("L "), u32), Motion::Left),
+        map(preceded(tag("R "), u32), Motion::Right),
+    ))(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Motion>, Error> {
+    Ok(parse_lines(input, motion)?)
+}
+
+pub fn tail_visits(motions: &[Motion], len: usize) -> usize {
+    let mut visited: HashSet<Point> = HashSet::new();
+    let mut snake = vec![Point::new(0, 0); len];
+    let mut moves = |count: u32, step: Point| {
+        for _ in 0..count {
+            snake[0] += step;
+            snake.windows_mut::<2>().for_each(|[head, tail]| {
+                if head.chebyshev(tail) > 1 {
+                    *tail += (*head - *tail).signum();
+                }
+            });
+            visited.insert(*snake.last().unwrap());
+        }
+    };
+
+    for motion in motions {
+        match motion {
+            Motion::Up(count) => moves(*count, Point::new(0, 1)),
+            Motion::Down(count) => moves(*count, Point::new(0, -1)),
+            Motion::Left(count) => moves(*count, Point::new(-1, 0)),
+            Motion::Right(count) => moves(*count, Point::new(1, 0)),
+        }
+    }
+
+    visited.len()
+}
+
+pub fn part1(motions: &[Motion]) -> usize {
+    tail_visits(motions, 2)
+}
+
+pub fn part2(motions: &[Motion]) -> usize {
+    tail_visits(motions, 10)
+}
+
+pub fn solve(input: &str) -> Result<(usize, usize), Error> {
+    let motions = parse(input)?;
+    Ok((part1(&motions), part2(&motions)))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let motions = parse(input)?;
+    Ok(match part {
+        Part::One => part1(&motions).to_string(),
+        Part::Two => part2(&motions).to_string(),
+    })
+}
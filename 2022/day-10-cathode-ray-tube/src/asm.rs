@@ -0,0 +1,483 @@
+use crate::{operation, Operation};
+use aoc_core::parse::{parse_line, tag, IResult, ParseError};
+use nom::{
+    branch::alt,
+    bytes::complete::take_while1,
+    character::complete::{space0, space1},
+    combinator::{map, opt, rest},
+    error::context,
+    sequence::{pair, preceded, terminated, tuple},
+};
+use std::{collections::HashMap, fmt};
+
+const WIDTH: usize = 40;
+const HEIGHT: usize = 6;
+
+/// An assembled program, with the instruction index each label points at.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Program {
+    pub operations: Vec<Operation>,
+    pub labels: Vec<(String, usize)>,
+}
+
+#[derive(Debug)]
+pub enum AsmError {
+    Parse(ParseError),
+    Unknown {
+        line: usize,
+        name: String,
+    },
+    DuplicateLabel {
+        line: usize,
+        label: String,
+    },
+    NestedMacro {
+        line: usize,
+    },
+    LabelInMacro {
+        line: usize,
+    },
+    UnmatchedEnd {
+        line: usize,
+    },
+    UnterminatedMacro {
+        line: usize,
+        name: String,
+    },
+    RecursiveMacro {
+        line: usize,
+        name: String,
+    },
+    /// No instruction stream draws pixel `pixel` (zero-based) of a pattern.
+    Undrawable {
+        line: usize,
+        pixel: usize,
+        cycle: usize,
+    },
+    Bitmap {
+        line: usize,
+    },
+    BitmapHeight {
+        rows: usize,
+    },
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsmError::Parse(err) => write!(f, "invalid assembly at {}", err),
+            AsmError::Unknown { line, name } => {
+                write!(f, "line {}: unknown instruction or macro `{}`", line, name)
+            }
+            AsmError::DuplicateLabel { line, label } => {
+                write!(f, "line {}: label `{}` is already defined", line, label)
+            }
+            AsmError::NestedMacro { line } => {
+                write!(f, "line {}: macros cannot be defined inside a macro", line)
+            }
+            AsmError::LabelInMacro { line } => {
+                write!(f, "line {}: labels cannot be defined inside a macro", line)
+            }
+            AsmError::UnmatchedEnd { line } => write!(f, "line {}: `end` outside a macro", line),
+            AsmError::UnterminatedMacro { line, name } => {
+                write!(f, "line {}: macro `{}` has no `end`", line, name)
+            }
+            AsmError::RecursiveMacro { line, name } => {
+                write!(f, "line {}: macro `{}` expands itself", line, name)
+            }
+            AsmError::Undrawable { line, pixel, cycle } => write!(
+                f,
+                "line {}: pixel {} of the pattern (row {}, column {}) cannot be drawn",
+                line,
+                pixel + 1,
+                (cycle / WIDTH) % HEIGHT + 1,
+                cycle % WIDTH + 1
+            ),
+            AsmError::Bitmap { line } => {
+                write!(f, "line {}: expected {} pixels of `#` or `.`", line, WIDTH)
+            }
+            AsmError::BitmapHeight { rows } => {
+                write!(f, "expected {} rows of pixels, found {}", HEIGHT, rows)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AsmError {}
+
+impl From<ParseError> for AsmError {
+    fn from(err: ParseError) -> Self {
+        AsmError::Parse(err)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Statement<'a> {
+    Operation(Operation),
+    Macro(&'a str),
+    End,
+    Draw(&'a str),
+    Call(&'a str),
+}
+
+/// A macro's statements with the lines they came from.
+type Body<'a> = Vec<(usize, Statement<'a>)>;
+
+struct Line<'a> {
+    label: Option<&'a str>,
+    statement: Option<Statement<'a>>,
+}
+
+fn name(input: &str) -> IResult<'_, &str> {
+    context(
+        "name",
+        take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_'),
+    )(input)
+}
+
+fn pattern(input: &str) -> IResult<'_, &str> {
+    context("pixels", take_while1(|c| c == '#' || c == '.'))(input)
+}
+
+fn statement(input: &str) -> IResult<'_, Statement<'_>> {
+    alt((
+        map(operation, Statement::Operation),
+        map(preceded(pair(tag("macro"), space1), name), Statement::Macro),
+        map(
+            preceded(pair(tag("draw"), space1), pattern),
+            Statement::Draw,
+        ),
+        map(name, |name| match name {
+            "end" => Statement::End,
+            _ => Statement::Call(name),
+        }),
+    ))(input)
+}
+
+fn line(input: &str) -> IResult<'_, Line<'_>> {
+    map(
+        tuple((
+            space0,
+            opt(terminated(name, pair(tag(":"), space0))),
+            opt(statement),
+            space0,
+            opt(preceded(tag(";"), rest)),
+        )),
+        |(_, label, statement, _, _)| Line { label, statement },
+    )(input)
+}
+
+/// Emits instructions while tracking the cycle count and X, which `draw`
+/// needs to know where the beam is and where the sprite starts.
+struct Assembler<'a> {
+    program: Program,
+    macros: HashMap<&'a str, Body<'a>>,
+    cycle: usize,
+    x: i32,
+}
+
+impl<'a> Assembler<'a> {
+    fn new() -> Self {
+        Assembler {
+            program: Program::default(),
+            macros: HashMap::new(),
+            cycle: 0,
+            x: 1,
+        }
+    }
+
+    fn emit(&mut self, operation: Operation) {
+        self.cycle += operation.cycles();
+        if let Operation::AddX(n) = operation {
+            self.x += n;
+        }
+        self.program.operations.push(operation);
+    }
+
+    fn label(&mut self, line: usize, label: &str) -> Result<(), AsmError> {
+        if self.program.labels.iter().any(|(l, _)| l == label) {
+            return Err(AsmError::DuplicateLabel {
+                line,
+                label: label.to_string(),
+            });
+        }
+        let pc = self.program.operations.len();
+        self.program.labels.push((label.to_string(), pc));
+        Ok(())
+    }
+
+    /// Assembles `statement`, with `expanding` holding the macros it is
+    /// nested in.
+    fn statement(
+        &mut self,
+        line: usize,
+        statement: Statement<'a>,
+        expanding: &mut Vec<&'a str>,
+    ) -> Result<(), AsmError> {
+        match statement {
+            Statement::Operation(operation) => self.emit(operation),
+            Statement::Draw(pattern) => {
+                let operations =
+                    draw(pattern, self.cycle, self.x).map_err(|pixel| AsmError::Undrawable {
+                        line,
+                        pixel,
+                        cycle: self.cycle + pixel,
+                    })?;
+                operations.into_iter().for_each(|op| self.emit(op));
+            }
+            Statement::Call(name) => {
+                if expanding.contains(&name) {
+                    return Err(AsmError::RecursiveMacro {
+                        line,
+                        name: name.to_string(),
+                    });
+                }
+                let body = self.macros.get(name).cloned().ok_or(AsmError::Unknown {
+                    line,
+                    name: name.to_string(),
+                })?;
+                expanding.push(name);
+                for (line, statement) in body {
+                    self.statement(line, statement, expanding)?;
+                }
+                expanding.pop();
+            }
+            Statement::Macro(_) => return Err(AsmError::NestedMacro { line }),
+            Statement::End => return Err(AsmError::UnmatchedEnd { line }),
+        }
+        Ok(())
+    }
+}
+
+/// Assembles `noop` and `addx` with `label:` markers, `;` comments,
+/// `macro NAME` ... `end` definitions and the `draw PIXELS` pseudo-op, which
+/// lights the given run of `#` and `.` from the current beam position.
+pub fn assemble(source: &str) -> Result<Program, AsmError> {
+    let mut assembler = Assembler::new();
+    let mut defining: Option<(usize, &str, Body)> = None;
+    for (i, text) in source.lines().enumerate() {
+        let Line { label, statement } = parse_line(i, text, line)?;
+        let line = i + 1;
+        match (&mut defining, statement) {
+            (Some(_), Some(Statement::Macro(_))) => return Err(AsmError::NestedMacro { line }),
+            (Some(_), Some(Statement::End)) => {
+                let (_, name, body) = defining.take().unwrap();
+                assembler.macros.insert(name, body);
+            }
+            (Some((_, _, body)), statement) => {
+                if label.is_some() {
+                    return Err(AsmError::LabelInMacro { line });
+                }
+                body.extend(statement.map(|statement| (line, statement)));
+            }
+            (None, Some(Statement::Macro(name))) => {
+                if let Some(label) = label {
+                    assembler.label(line, label)?;
+                }
+                defining = Some((line, name, Vec::new()));
+            }
+            (None, statement) => {
+                if let Some(label) = label {
+                    assembler.label(line, label)?;
+                }
+                if let Some(statement) = statement {
+                    assembler.statement(line, statement, &mut Vec::new())?;
+                }
+            }
+        }
+    }
+    if let Some((line, name, _)) = defining {
+        return Err(AsmError::UnterminatedMacro {
+            line,
+            name: name.to_string(),
+        });
+    }
+    Ok(assembler.program)
+}
+
+/// How many instructions reach a state, and the last of them with the value
+/// of X it started from.
+type Reach = (usize, Option<(i32, Operation)>);
+
+/// Finds the fewest instructions that draw `pattern` starting at `cycle`
+/// (zero-based) with X at `x`, finishing exactly as the last pixel is drawn.
+/// On failure, returns the first pixel that no instruction stream gets past.
+fn draw(pattern: &str, cycle: usize, x: i32) -> Result<Vec<Operation>, usize> {
+    let pixels: Vec<bool> = pattern.chars().map(|c| c == '#').collect();
+    let n = pixels.len();
+    let mut values: Vec<i32> = (-2..=WIDTH as i32 + 1).collect();
+    if !values.contains(&x) {
+        values.push(x);
+    }
+    let lit = |i: usize, x: i32| {
+        let column = ((cycle + i) % WIDTH) as i32;
+        ((x - column).abs() <= 1) == pixels[i]
+    };
+    // For each pixel and value of X, the fewest instructions that get there
+    // and the last of them.
+    let mut reached: Vec<HashMap<i32, Reach>> = vec![HashMap::new(); n + 1];
+    reached[0].insert(x, (0, None));
+    let mut furthest = 0;
+    for i in 0..n {
+        let mut from: Vec<(i32, usize)> = reached[i].iter().map(|(&x, &(c, _))| (x, c)).collect();
+        from.sort_unstable();
+        for (x, count) in from {
+            if !lit(i, x) {
+                continue;
+            }
+            let mut moves = vec![(i + 1, x, Operation::NoOp)];
+            if i + 1 < n && lit(i + 1, x) {
+                moves.extend(
+                    values
+                        .iter()
+                        .map(|&to| (i + 2, to, Operation::AddX(to - x))),
+                );
+            }
+            for (next, to, operation) in moves {
+                furthest = furthest.max(next);
+                let best = reached[next].entry(to).or_insert((usize::MAX, None));
+                if count + 1 < best.0 {
+                    *best = (count + 1, Some((x, operation)));
+                }
+            }
+        }
+    }
+    let (mut x, _) = reached[n]
+        .iter()
+        .map(|(&to, &(count, _))| (to, (count, (to - x).abs())))
+        .min_by_key(|&(to, key)| (key, to))
+        .ok_or(furthest)?;
+    let mut i = n;
+    let mut operations = Vec::new();
+    while let (_, Some((previous, operation))) = reached[i][&x] {
+        i -= operation.cycles();
+        x = previous;
+        operations.push(operation);
+    }
+    operations.reverse();
+    Ok(operations)
+}
+
+/// Generates a program that renders a bitmap of six rows of 40 `#` and `.`
+/// on the CRT.
+pub fn compile(bitmap: &str) -> Result<Vec<Operation>, AsmError> {
+    let rows: Vec<&str> = bitmap.lines().collect();
+    if rows.len() != HEIGHT {
+        return Err(AsmError::BitmapHeight { rows: rows.len() });
+    }
+    if let Some(i) = rows
+        .iter()
+        .position(|row| row.len() != WIDTH || pattern(row) != Ok(("", row)))
+    {
+        return Err(AsmError::Bitmap { line: i + 1 });
+    }
+    draw(&rows.concat(), 0, 1).map_err(|pixel| AsmError::Undrawable {
+        line: pixel / WIDTH + 1,
+        pixel,
+        cycle: pixel,
+    })
+}
+
+/// Prints a program one instruction per line, indented under its labels,
+/// with the cycles it runs during and the value of X meanwhile.
+pub fn disassemble(program: &Program) -> String {
+    let mut lines = Vec::new();
+    let (mut cycle, mut x) = (1, 1);
+    let labels = |pc: usize| {
+        program
+            .labels
+            .iter()
+            .filter(move |&&(_, at)| at == pc)
+            .map(|(label, _)| format!("{}:", label))
+    };
+    for (pc, operation) in program.operations.iter().enumerate() {
+        lines.extend(labels(pc));
+        let cycles = match operation.cycles() {
+            1 => format!("cycle {}", cycle),
+            n => format!("cycles {}-{}", cycle, cycle + n - 1),
+        };
+        lines.push(format!(
+            "    {:<10}; {}, x={}",
+            operation.to_string(),
+            cycles,
+            x
+        ));
+        cycle += operation.cycles();
+        if let Operation::AddX(n) = operation {
+            x += n;
+        }
+    }
+    lines.extend(labels(program.operations.len()));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        asm::{assemble, compile, disassemble, AsmError},
+        render, Operation,
+    };
+
+    const SOURCE: &str = "\
+; blink the sprite
+macro twice
+    noop
+    noop
+end
+start: addx 3 ; move right
+    twice
+loop:
+    draw ##..
+    addx -1
+";
+
+    #[test]
+    fn assembles_labels_and_macros() {
+        let program = assemble(SOURCE).unwrap();
+        assert_eq!(
+            &program.operations[..3],
+            &[Operation::AddX(3), Operation::NoOp, Operation::NoOp]
+        );
+        assert_eq!(program.operations.last(), Some(&Operation::AddX(-1)));
+        assert_eq!(
+            program.labels,
+            vec![("start".to_string(), 0), ("loop".to_string(), 3)]
+        );
+    }
+
+    #[test]
+    fn disassembly_reassembles() {
+        let program = assemble(SOURCE).unwrap();
+        assert_eq!(assemble(&disassemble(&program)).unwrap(), program);
+    }
+
+    #[test]
+    fn reports_errors_by_line() {
+        let err = assemble("noop\nblink\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: unknown instruction or macro `blink`"
+        );
+        assert!(matches!(
+            assemble("macro a\n a\nend\na"),
+            Err(AsmError::RecursiveMacro { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn compiles_bitmaps() {
+        let bitmap = "\
+####.#....###..#....####..##..####.#....
+#....#....#..#.#.......#.#..#....#.#....
+###..#....#..#.#......#..#......#..#....
+#....#....###..#.....#...#.##..#...#....
+#....#....#....#....#....#..#.#....#....
+####.####.#....####.####..###.####.####.";
+        assert_eq!(render(&compile(bitmap).unwrap()), bitmap);
+        let dark = bitmap.replacen('#', ".", 1);
+        assert!(matches!(
+            compile(&dark),
+            Err(AsmError::Undrawable { pixel: 0, .. })
+        ));
+    }
+}
@@ -0,0 +1,286 @@
+use crate::Operation;
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Register {
+    X,
+}
+
+impl Register {
+    pub const ALL: [Register; 1] = [Register::X];
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Register::X => "x",
+        })
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Registers([i32; Register::ALL.len()]);
+
+impl Default for Registers {
+    fn default() -> Self {
+        Registers([1])
+    }
+}
+
+impl Index<Register> for Registers {
+    type Output = i32;
+
+    fn index(&self, register: Register) -> &i32 {
+        &self.0[register as usize]
+    }
+}
+
+impl IndexMut<Register> for Registers {
+    fn index_mut(&mut self, register: Register) -> &mut i32 {
+        &mut self.0[register as usize]
+    }
+}
+
+/// The machine during one cycle: the registers hold the values seen by that
+/// cycle, before the instruction at `pc` completes.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct State {
+    pub cycle: usize,
+    pub pc: usize,
+    pub operation: Operation,
+    pub registers: Registers,
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cycle {:>3}  pc {:>3}  {:<9}",
+            self.cycle,
+            self.pc,
+            self.operation.to_string()
+        )?;
+        for register in Register::ALL {
+            write!(f, "  {}={}", register, self.registers[register])?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Breakpoint {
+    Cycle(usize),
+    Register(Register, i32),
+}
+
+impl Breakpoint {
+    fn hit(&self, state: &State) -> bool {
+        match *self {
+            Breakpoint::Cycle(cycle) => state.cycle == cycle,
+            Breakpoint::Register(register, value) => state.registers[register] == value,
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Breakpoint::Cycle(cycle) => write!(f, "cycle={}", cycle),
+            Breakpoint::Register(register, value) => write!(f, "{}={}", register, value),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseBreakpointError(String);
+
+impl fmt::Display for ParseBreakpointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid breakpoint `{}`, expected cycle=N or x=VALUE",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseBreakpointError {}
+
+impl FromStr for Breakpoint {
+    type Err = ParseBreakpointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseBreakpointError(s.to_string());
+        let (name, value) = s.split_once('=').ok_or_else(err)?;
+        match name {
+            "cycle" => value.parse().map(Breakpoint::Cycle).map_err(|_| err()),
+            _ => {
+                let register = Register::ALL
+                    .into_iter()
+                    .find(|register| register.to_string() == name)
+                    .ok_or_else(err)?;
+                let value = value.parse().map_err(|_| err())?;
+                Ok(Breakpoint::Register(register, value))
+            }
+        }
+    }
+}
+
+/// Why [`Cpu::run`] returned.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Stop {
+    Break(Breakpoint, State),
+    Halted,
+}
+
+/// Executes a program one cycle at a time; as an iterator it yields the
+/// state during each cycle until the program ends.
+pub struct Cpu<'a> {
+    program: &'a [Operation],
+    pc: usize,
+    cycle: usize,
+    /// Cycles already spent on the instruction at `pc`.
+    busy: usize,
+    registers: Registers,
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl<'a> Cpu<'a> {
+    pub fn new(program: &'a [Operation]) -> Self {
+        Cpu {
+            program,
+            pc: 0,
+            cycle: 0,
+            busy: 0,
+            registers: Registers::default(),
+            breakpoints: Vec::new(),
+        }
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// The number of cycles completed so far.
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    pub fn halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    /// Runs one cycle, returning the state during it, or `None` once the
+    /// program has ended.
+    pub fn step(&mut self) -> Option<State> {
+        let &operation = self.program.get(self.pc)?;
+        self.cycle += 1;
+        let state = State {
+            cycle: self.cycle,
+            pc: self.pc,
+            operation,
+            registers: self.registers,
+        };
+        self.busy += 1;
+        if self.busy == operation.cycles() {
+            match operation {
+                Operation::AddX(n) => self.registers[Register::X] += n,
+                Operation::NoOp => {}
+            }
+            self.pc += 1;
+            self.busy = 0;
+        }
+        Some(state)
+    }
+
+    /// Runs up to and including `cycle`, returning the state during it, or
+    /// `None` if the program ends first.
+    pub fn run_until(&mut self, cycle: usize) -> Option<State> {
+        while self.cycle < cycle {
+            let state = self.step()?;
+            if state.cycle == cycle {
+                return Some(state);
+            }
+        }
+        None
+    }
+
+    /// Runs until a breakpoint is hit or the program ends, passing the state
+    /// of every cycle to `on_cycle`. Running again resumes after the cycle
+    /// that hit.
+    pub fn run(&mut self, mut on_cycle: impl FnMut(&State)) -> Stop {
+        while let Some(state) = self.step() {
+            on_cycle(&state);
+            if let Some(&breakpoint) = self.breakpoints.iter().find(|b| b.hit(&state)) {
+                return Stop::Break(breakpoint, state);
+            }
+        }
+        Stop::Halted
+    }
+}
+
+impl Iterator for Cpu<'_> {
+    type Item = State;
+
+    fn next(&mut self) -> Option<State> {
+        self.step()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cpu::{Breakpoint, Cpu, Register, Stop},
+        Operation,
+    };
+
+    const PROGRAM: [Operation; 3] = [Operation::NoOp, Operation::AddX(3), Operation::AddX(-5)];
+
+    #[test]
+    fn values_during_each_cycle() {
+        let mut cpu = Cpu::new(&PROGRAM);
+        let values: Vec<i32> = cpu.by_ref().map(|s| s.registers[Register::X]).collect();
+        assert_eq!(values, vec![1, 1, 1, 4, 4]);
+        assert_eq!(cpu.registers()[Register::X], -1);
+        assert!(cpu.halted());
+    }
+
+    #[test]
+    fn runs_until_cycle() {
+        let mut cpu = Cpu::new(&PROGRAM);
+        let state = cpu.run_until(4).unwrap();
+        assert_eq!((state.pc, state.registers[Register::X]), (2, 4));
+        assert_eq!(cpu.run_until(9), None);
+    }
+
+    #[test]
+    fn stops_at_breakpoints() {
+        let mut cpu = Cpu::new(&PROGRAM);
+        cpu.add_breakpoint("x=4".parse().unwrap());
+        cpu.add_breakpoint(Breakpoint::Cycle(2));
+        let mut stops = vec![];
+        while let Stop::Break(breakpoint, state) = cpu.run(|_| {}) {
+            stops.push((breakpoint, state.cycle));
+        }
+        assert_eq!(
+            stops,
+            vec![
+                (Breakpoint::Cycle(2), 2),
+                (Breakpoint::Register(Register::X, 4), 4),
+                (Breakpoint::Register(Register::X, 4), 5)
+            ]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use aoc_core::{
+    parse::{parse_lines, tag, IResult, ParseError},
+    Part,
+};
+use nom::{branch::alt, character::complete::i32, combinator::map, sequence::preceded};
+use std::fmt;
+
+mod asm;
+mod cpu;
+mod ocr;
+
+pub use asm::{assemble, compile, disassemble, AsmError, Program};
+pub use cpu::{Breakpoint, Cpu, ParseBreakpointError, Register, Registers, State, Stop};
+pub use ocr::OcrError;
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    Ocr(OcrError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid instruction at {}", err),
+            Error::Ocr(err) => write!(f, "cannot read the CRT: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Operation {
+    AddX(i32),
+    NoOp,
+}
+
+impl Operation {
+    /// How many cycles the instruction takes to complete.
+    pub fn cycles(&self) -> usize {
+        match self {
+            Operation::AddX(_) => 2,
+            Operation::NoOp => 1,
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operation::AddX(n) => write!(f, "addx {}", n),
+            Operation::NoOp => write!(f, "noop"),
+        }
+    }
+}
+
+fn operation(input: &str) -> IResult<'_, Operation> {
+    alt((
+        map(tag("noop"), |_| Operation::NoOp),
+        map(preceded(tag("addx "), i32), Operation::AddX),
+    ))(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Operation>, Error> {
+    Ok(parse_lines(input, operation)?)
+}
+
+pub fn part1(program: &[Operation]) -> i32 {
+    Cpu::new(program)
+        .filter(|state| state.cycle % 40 == 20)
+        .map(|state| state.cycle as i32 * state.registers[Register::X])
+        .sum()
+}
+
+/// The CRT's pixels, one row of `#` and `.` per line.
+pub fn render(program: &[Operation]) -> String {
+    Cpu::new(program)
+        .map(|state| {
+            let column = (state.cycle as i32 - 1) % 40;
+            let x = state.registers[Register::X];
+            if (x - 1..=x + 1).contains(&column) {
+                '#'
+            } else {
+                '.'
+            }
+        })
+        .collect::<Vec<char>>()
+        .chunks(40)
+        .map(|c| c.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn part2(program: &[Operation]) -> Result<String, Error> {
+    ocr::read(&render(program)).map_err(Error::Ocr)
+}
+
+pub fn solve(input: &str) -> Result<(i32, String), Error> {
+    let program = parse(input)?;
+    Ok((part1(&program), part2(&program)?))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let program = parse(input)?;
+    Ok(match part {
+        Part::One => part1(&program).to_string(),
+        Part::Two => part2(&program)?,
+    })
+}
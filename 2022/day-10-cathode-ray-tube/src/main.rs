@@ -1,87 +1,106 @@
+use aoc_core::{Input, Output, Part};
 use clap::Parser;
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    combinator::{map, rest},
-    sequence::{pair, separated_pair},
-    IResult,
+use day_10_cathode_ray_tube::{
+    assemble, compile, disassemble, parse, render, solve, solve_part, Breakpoint, Cpu, Operation,
+    Stop,
 };
-use std::io;
 
 #[derive(Parser)]
 struct Args {
     #[arg(long, default_value_t = false)]
     draw: bool,
-}
-
-#[derive(Debug)]
-enum Operation {
-    AddX(i32),
-    NoOp,
-}
-
-fn operation(input: &str) -> IResult<&str, Operation> {
-    map(
-        alt((
-            pair(tag("noop"), tag("")),
-            separated_pair(tag("addx"), tag(" "), rest),
-        )),
-        |(operation, argument)| match operation {
-            "noop" => Operation::NoOp,
-            "addx" => Operation::AddX(argument.parse().unwrap()),
-            _ => panic!("invalid operation: {}", operation),
-        },
-    )(input)
+    /// Print the CRT's pixels instead of reading the letters off them
+    #[arg(long, default_value_t = false)]
+    raw: bool,
+    /// Print the state of the CPU during every cycle
+    #[arg(long, default_value_t = false)]
+    trace: bool,
+    /// Report when the CPU reaches a cycle or register value, e.g. cycle=20
+    /// or x=-3; may be repeated
+    #[arg(long = "break", value_name = "BREAKPOINT")]
+    breakpoints: Vec<Breakpoint>,
+    /// Read the input as assembly with labels, comments, macros and `draw`,
+    /// and print the plain program
+    #[arg(long, default_value_t = false, conflicts_with_all = ["disassemble", "compile"])]
+    assemble: bool,
+    /// Assemble the input and pretty-print it with cycles and values of X
+    #[arg(long, default_value_t = false, conflicts_with = "compile")]
+    disassemble: bool,
+    /// Read the input as six rows of 40 `#` and `.` and print a program
+    /// that draws them
+    #[arg(long, default_value_t = false)]
+    compile: bool,
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let Args { draw } = Args::parse();
-    let input: Vec<Operation> = io::stdin()
-        .lines()
-        .map(|line| {
-            operation(&line.unwrap())
-                .map(|(_, operation)| operation)
-                .unwrap()
-        })
-        .collect();
-    let mut x = 1;
-    let mut values = vec![];
-
-    input.iter().for_each(|op| match op {
-        Operation::AddX(num) => {
-            values.push(x);
-            values.push(x);
-            x += num;
+    let Args {
+        draw,
+        raw,
+        trace,
+        breakpoints,
+        assemble: assembly,
+        disassemble: pretty,
+        compile: bitmap,
+        input,
+        output,
+    } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if assembly || pretty {
+        let program = assemble(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        if pretty {
+            println!("{}", disassemble(&program));
+        } else {
+            print_program(&program.operations);
         }
-        Operation::NoOp => values.push(x),
-    });
-
-    let result = if draw {
-        values
-            .iter()
-            .enumerate()
-            .map(|(i, &x)| {
-                if (x - 1..=x + 1).contains(&(i as i32 % 40)) {
-                    '#'
-                } else {
-                    '.'
-                }
-            })
-            .collect::<Vec<char>>()
-            .chunks(40)
-            .map(|c| c.iter().collect::<String>())
-            .collect::<Vec<String>>()
-            .join("\n")
+        return;
+    }
+    if bitmap {
+        let program = compile(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        print_program(&program);
+        return;
+    }
+    // The modes above turn the input into something other than answers,
+    // so they take precedence over reporting them.
+    if output.report(env!("CARGO_MANIFEST_DIR"), &input, &Part::ALL, solve_part) {
+        return;
+    }
+    if raw {
+        let program = parse(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", render(&program));
+        return;
+    }
+    if trace || !breakpoints.is_empty() {
+        let program = parse(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        let mut cpu = Cpu::new(&program);
+        breakpoints
+            .into_iter()
+            .for_each(|breakpoint| cpu.add_breakpoint(breakpoint));
+        while let Stop::Break(breakpoint, state) = cpu.run(|state| {
+            if trace {
+                println!("{}", state);
+            }
+        }) {
+            println!("break on {} at {}", breakpoint, state);
+        }
+        return;
+    }
+    if draw {
+        let answer = solve_part(&input, Part::Two).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", answer);
     } else {
-        values
-            .iter()
-            .enumerate()
-            .skip(19)
-            .step_by(40)
-            .map(|(i, &x)| (i as i32 + 1) * x)
-            .sum::<i32>()
-            .to_string()
-    };
+        let (part1, part2) = solve(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        aoc_core::print_answers(part1, part2);
+    }
+}
 
-    println!("{}", result);
+fn print_program(program: &[Operation]) {
+    for operation in program {
+        println!("{}", operation);
+    }
 }
@@ -0,0 +1,134 @@
+use std::fmt;
+
+const HEIGHT: usize = 6;
+const WIDTH: usize = 4;
+
+/// The capital letters of the 4x6 font, as far as they're known.
+const FONT: [(char, [&str; HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###.", ".#..", ".#..", ".#..", ".#..", "###."]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum OcrError {
+    Height {
+        rows: usize,
+    },
+    /// The first column (one-based) of each glyph that isn't a letter.
+    Unrecognised {
+        columns: Vec<usize>,
+    },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Height { rows } => {
+                write!(f, "expected {} rows of pixels, found {}", HEIGHT, rows)
+            }
+            OcrError::Unrecognised { columns } => {
+                let columns: Vec<String> = columns.iter().map(usize::to_string).collect();
+                match columns.len() {
+                    1 => write!(f, "unrecognised glyph at column {}", columns[0]),
+                    _ => write!(f, "unrecognised glyphs at columns {}", columns.join(", ")),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+fn glyph(rows: &[&str], column: usize) -> Option<char> {
+    let pixels: Vec<&str> = rows
+        .iter()
+        .map(|row| row.get(column..column + WIDTH).unwrap_or(""))
+        .collect();
+    FONT.iter()
+        .find(|(_, glyph)| glyph[..] == pixels[..])
+        .map(|&(c, _)| c)
+}
+
+/// Reads the letters from rows of `#` and `.`, with a blank column after
+/// each glyph.
+pub fn read(bitmap: &str) -> Result<String, OcrError> {
+    let rows: Vec<&str> = bitmap.lines().collect();
+    if rows.len() != HEIGHT {
+        return Err(OcrError::Height { rows: rows.len() });
+    }
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut letters = String::new();
+    let mut unrecognised = Vec::new();
+    for column in (0..width).step_by(WIDTH + 1) {
+        match glyph(&rows, column) {
+            Some(c) => letters.push(c),
+            None => unrecognised.push(column + 1),
+        }
+    }
+    if unrecognised.is_empty() {
+        Ok(letters)
+    } else {
+        Err(OcrError::Unrecognised {
+            columns: unrecognised,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ocr::{read, OcrError, FONT};
+
+    fn render(letters: &[[&str; 6]]) -> String {
+        (0..6)
+            .map(|y| {
+                letters
+                    .iter()
+                    .map(|glyph| format!("{}.", glyph[y]))
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reads_every_letter() {
+        let glyphs: Vec<[&str; 6]> = FONT.iter().map(|&(_, glyph)| glyph).collect();
+        let letters: String = FONT.iter().map(|&(c, _)| c).collect();
+        assert_eq!(read(&render(&glyphs)), Ok(letters));
+    }
+
+    #[test]
+    fn lists_unrecognised_glyphs() {
+        let (_, h) = FONT[6];
+        let blob = ["####"; 6];
+        assert_eq!(
+            read(&render(&[h, blob, h, blob])),
+            Err(OcrError::Unrecognised {
+                columns: vec![6, 16]
+            })
+        );
+        assert_eq!(
+            OcrError::Unrecognised {
+                columns: vec![6, 16]
+            }
+            .to_string(),
+            "unrecognised glyphs at columns 6, 16"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use aoc_core::parse::{tag, IResult};
+use nom::{
+    branch::alt,
+    character::complete::{space0, u64},
+    combinator::{map, value},
+    multi::fold_many0,
+    sequence::{delimited, pair},
+};
+use num_bigint::BigUint;
+use std::fmt;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl Op {
+    fn precedence(&self) -> u8 {
+        match self {
+            Op::Add | Op::Sub => 1,
+            Op::Mul | Op::Div | Op::Rem => 2,
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+        })
+    }
+}
+
+/// The right-hand side of a monkey's `new = ...` operation.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Expr {
+    Old,
+    Num(u64),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum EvalError {
+    Overflow,
+    Negative,
+    DivideByZero,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvalError::Overflow => write!(f, "the worry level overflowed"),
+            EvalError::Negative => write!(f, "the worry level went below zero"),
+            EvalError::DivideByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// A worry level that expressions can be evaluated over.
+pub trait Value: Clone {
+    fn num(n: u64) -> Self;
+    fn apply(op: Op, lhs: Self, rhs: Self) -> Result<Self, EvalError>;
+    /// `lhs * rhs % modulus`, without overflowing on the way.
+    fn mul_mod(lhs: Self, rhs: Self, modulus: u64) -> Result<Self, EvalError>;
+    fn divisible_by(&self, divisor: u64) -> bool;
+}
+
+impl Value for u64 {
+    fn num(n: u64) -> Self {
+        n
+    }
+
+    fn apply(op: Op, lhs: u64, rhs: u64) -> Result<u64, EvalError> {
+        match op {
+            Op::Add => lhs.checked_add(rhs).ok_or(EvalError::Overflow),
+            Op::Sub => lhs.checked_sub(rhs).ok_or(EvalError::Negative),
+            Op::Mul => lhs.checked_mul(rhs).ok_or(EvalError::Overflow),
+            Op::Div => lhs.checked_div(rhs).ok_or(EvalError::DivideByZero),
+            Op::Rem => lhs.checked_rem(rhs).ok_or(EvalError::DivideByZero),
+        }
+    }
+
+    fn mul_mod(lhs: u64, rhs: u64, modulus: u64) -> Result<u64, EvalError> {
+        let product = u128::from(lhs) * u128::from(rhs);
+        let rem = product
+            .checked_rem(u128::from(modulus))
+            .ok_or(EvalError::DivideByZero)?;
+        Ok(rem as u64)
+    }
+
+    fn divisible_by(&self, divisor: u64) -> bool {
+        self.is_multiple_of(divisor)
+    }
+}
+
+impl Value for BigUint {
+    fn num(n: u64) -> Self {
+        BigUint::from(n)
+    }
+
+    fn apply(op: Op, lhs: BigUint, rhs: BigUint) -> Result<BigUint, EvalError> {
+        let zero = BigUint::default();
+        match op {
+            Op::Add => Ok(lhs + rhs),
+            Op::Sub if lhs < rhs => Err(EvalError::Negative),
+            Op::Sub => Ok(lhs - rhs),
+            Op::Mul => Ok(lhs * rhs),
+            Op::Div | Op::Rem if rhs == zero => Err(EvalError::DivideByZero),
+            Op::Div => Ok(lhs / rhs),
+            Op::Rem => Ok(lhs % rhs),
+        }
+    }
+
+    fn mul_mod(lhs: BigUint, rhs: BigUint, modulus: u64) -> Result<BigUint, EvalError> {
+        BigUint::apply(Op::Rem, lhs * rhs, BigUint::from(modulus))
+    }
+
+    fn divisible_by(&self, divisor: u64) -> bool {
+        self % divisor == BigUint::default()
+    }
+}
+
+impl Expr {
+    pub fn eval<T: Value>(&self, old: &T) -> Result<T, EvalError> {
+        match self {
+            Expr::Old => Ok(old.clone()),
+            Expr::Num(n) => Ok(T::num(*n)),
+            Expr::Binary(op, lhs, rhs) => T::apply(*op, lhs.eval(old)?, rhs.eval(old)?),
+        }
+    }
+
+    /// Evaluates the expression modulo `modulus`, reducing after every
+    /// operation so that neither products nor differences leave its range.
+    /// Division gives wrong answers this way; see [`Expr::divides`].
+    pub fn eval_mod<T: Value>(&self, old: &T, modulus: u64) -> Result<T, EvalError> {
+        let m = || T::num(modulus);
+        let value = match self {
+            Expr::Old => old.clone(),
+            Expr::Num(n) => T::num(*n),
+            Expr::Binary(Op::Sub, lhs, rhs) => {
+                // a - b is a + (m - b), which stays above zero.
+                let rhs = T::apply(Op::Sub, m(), rhs.eval_mod(old, modulus)?)?;
+                T::apply(Op::Add, lhs.eval_mod(old, modulus)?, rhs)?
+            }
+            Expr::Binary(Op::Mul, lhs, rhs) => T::mul_mod(
+                lhs.eval_mod(old, modulus)?,
+                rhs.eval_mod(old, modulus)?,
+                modulus,
+            )?,
+            Expr::Binary(op, lhs, rhs) => T::apply(
+                *op,
+                lhs.eval_mod(old, modulus)?,
+                rhs.eval_mod(old, modulus)?,
+            )?,
+        };
+        T::apply(Op::Rem, value, m())
+    }
+
+    /// Whether any part of the expression divides, which loses information
+    /// once worry levels are only known modulo some number.
+    pub fn divides(&self) -> bool {
+        match self {
+            Expr::Old | Expr::Num(_) => false,
+            Expr::Binary(op, lhs, rhs) => {
+                matches!(op, Op::Div | Op::Rem) || lhs.divides() || rhs.divides()
+            }
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Binary(op, _, _) => op.precedence(),
+            _ => u8::MAX,
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Old => write!(f, "old"),
+            Expr::Num(n) => write!(f, "{}", n),
+            Expr::Binary(op, lhs, rhs) => {
+                // Operators are left-associative, so only the right operand
+                // needs parentheses at equal precedence.
+                if lhs.precedence() < op.precedence() {
+                    write!(f, "({})", lhs)?;
+                } else {
+                    write!(f, "{}", lhs)?;
+                }
+                write!(f, " {} ", op)?;
+                if rhs.precedence() <= op.precedence() {
+                    write!(f, "({})", rhs)
+                } else {
+                    write!(f, "{}", rhs)
+                }
+            }
+        }
+    }
+}
+
+fn factor(input: &str) -> IResult<'_, Expr> {
+    alt((
+        value(Expr::Old, tag("old")),
+        map(u64, Expr::Num),
+        delimited(pair(tag("("), space0), expr, pair(space0, tag(")"))),
+    ))(input)
+}
+
+fn additive(input: &str) -> IResult<'_, Op> {
+    alt((value(Op::Add, tag("+")), value(Op::Sub, tag("-"))))(input)
+}
+
+fn multiplicative(input: &str) -> IResult<'_, Op> {
+    alt((
+        value(Op::Mul, tag("*")),
+        value(Op::Div, tag("/")),
+        value(Op::Rem, tag("%")),
+    ))(input)
+}
+
+/// Parses a left-associative chain of `operand`s joined by `op`.
+fn binary<'a>(
+    operand: fn(&'a str) -> IResult<'a, Expr>,
+    op: fn(&'a str) -> IResult<'a, Op>,
+) -> impl FnMut(&'a str) -> IResult<'a, Expr> {
+    move |input| {
+        let (input, first) = operand(input)?;
+        fold_many0(
+            pair(delimited(space0, op, space0), operand),
+            move || first.clone(),
+            |lhs, (op, rhs)| Expr::Binary(op, Box::new(lhs), Box::new(rhs)),
+        )(input)
+    }
+}
+
+fn term(input: &str) -> IResult<'_, Expr> {
+    binary(factor, multiplicative)(input)
+}
+
+pub fn expr(input: &str) -> IResult<'_, Expr> {
+    binary(term, additive)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expr::{expr, EvalError, Expr};
+    use num_bigint::BigUint;
+
+    fn parse(input: &str) -> Expr {
+        let (rest, expr) = expr(input).unwrap();
+        assert_eq!(rest, "");
+        expr
+    }
+
+    #[test]
+    fn evaluates_with_precedence() {
+        assert_eq!(parse("old * 19").eval(&2u64), Ok(38));
+        assert_eq!(parse("old + old * old").eval(&3u64), Ok(12));
+        assert_eq!(parse("(old + 1) * (old - 1) % 7").eval(&5u64), Ok(3));
+        assert_eq!(parse("old - 2 - 1").eval(&5u64), Ok(2));
+        assert_eq!(parse("old / 2 / 2").eval(&12u64), Ok(3));
+        assert_eq!(parse("old - 6").eval(&5u64), Err(EvalError::Negative));
+        assert_eq!(
+            parse("3 / (old - 5)").eval(&5u64),
+            Err(EvalError::DivideByZero)
+        );
+        assert_eq!(
+            parse("old * old").eval(&BigUint::from(u64::MAX)),
+            Ok(BigUint::from(u64::MAX) * u64::MAX)
+        );
+    }
+
+    #[test]
+    fn evaluates_modulo() {
+        assert_eq!(parse("old - 6").eval_mod(&5u64, 7), Ok(6));
+        assert_eq!(parse("(old - 9) * 3 + old").eval_mod(&5u64, 7), Ok(0));
+        assert_eq!(
+            parse("old * old * old").eval_mod(&(u64::MAX - 1), 1000003),
+            Ok((BigUint::from(u64::MAX - 1).pow(3) % 1000003u64)
+                .try_into()
+                .unwrap())
+        );
+    }
+
+    #[test]
+    fn displays_minimal_parentheses() {
+        for input in [
+            "old * 19",
+            "(old + 1) * old",
+            "old - (2 - old)",
+            "old / 2 * 3",
+        ] {
+            assert_eq!(parse(input).to_string(), input);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::Monkey;
+
+/// What one monkey did during a round: how many items it inspected, and the
+/// items it held once the round was over.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Turn {
+    pub inspections: u64,
+    pub items: Vec<usize>,
+}
+
+/// Every monkey's turn in every round, and how many items each monkey threw
+/// when its test passed and when it failed.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct History {
+    pub rounds: Vec<Vec<Turn>>,
+    pub throws: Vec<[u64; 2]>,
+}
+
+impl History {
+    pub(crate) fn new(monkeys: usize) -> Self {
+        History {
+            rounds: Vec::new(),
+            throws: vec![[0; 2]; monkeys],
+        }
+    }
+
+    /// One line per monkey per round, counting rounds from one, with the
+    /// items it held as space-separated numbers.
+    pub fn csv(&self) -> String {
+        let mut lines = vec!["round,monkey,inspections,total_inspections,held,items".to_string()];
+        let mut totals = vec![0; self.throws.len()];
+        for (round, turns) in self.rounds.iter().enumerate() {
+            for (monkey, turn) in turns.iter().enumerate() {
+                totals[monkey] += turn.inspections;
+                let items: Vec<String> = turn.items.iter().map(usize::to_string).collect();
+                lines.push(format!(
+                    "{},{},{},{},{},{}",
+                    round + 1,
+                    monkey,
+                    turn.inspections,
+                    totals[monkey],
+                    turn.items.len(),
+                    items.join(" ")
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// A Graphviz digraph of who throws to whom, with each edge labelled by
+    /// its throw count and drawn thicker the more it was used.
+    pub fn dot(&self, monkeys: &[Monkey]) -> String {
+        let most = self
+            .throws
+            .iter()
+            .flatten()
+            .max()
+            .copied()
+            .unwrap_or(0)
+            .max(1);
+        let mut lines = vec!["digraph monkeys {".to_string()];
+        for (i, monkey) in monkeys.iter().enumerate() {
+            lines.push(format!(
+                "    {} [label=\"monkey {}\\ndivisible by {}\"];",
+                i, i, monkey.divisor
+            ));
+        }
+        for (i, (monkey, throws)) in monkeys.iter().zip(&self.throws).enumerate() {
+            for (to, test, count) in [(monkey.mt, true, throws[0]), (monkey.mf, false, throws[1])] {
+                lines.push(format!(
+                    "    {} -> {} [label=\"{}: {}\", penwidth={:.2}{}];",
+                    i,
+                    to,
+                    test,
+                    count,
+                    1.0 + 4.0 * count as f64 / most as f64,
+                    if test { "" } else { ", style=dashed" }
+                ));
+            }
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
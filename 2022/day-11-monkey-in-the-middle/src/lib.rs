@@ -0,0 +1,526 @@
+use aoc_core::{
+    parse::{parse_all, tag, IResult, ParseError},
+    Part,
+};
+use clap::ValueEnum;
+use nom::{
+    character::complete::{u32, u64},
+    combinator::{cut, map},
+    multi::separated_list1,
+    sequence::{preceded, tuple},
+};
+use num_bigint::BigUint;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+};
+
+mod expr;
+mod history;
+
+pub use expr::{EvalError, Expr, Op, Value};
+pub use history::{History, Turn};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    Eval { monkey: usize, err: EvalError },
+    Relief { monkey: usize, relief: Relief },
+    ZeroDivisor { monkey: usize },
+    UnknownTarget { monkey: usize, target: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid monkey notes at {}", err),
+            Error::Eval { monkey, err } => write!(f, "monkey {}: {}", monkey, err),
+            Error::Relief { monkey, relief } => write!(
+                f,
+                "monkey {} divides its worry level, which {} relief cannot keep track of",
+                monkey,
+                relief.to_possible_value().unwrap().get_name()
+            ),
+            Error::ZeroDivisor { monkey } => {
+                write!(f, "monkey {} tests for divisibility by zero", monkey)
+            }
+            Error::UnknownTarget { monkey, target } => {
+                write!(
+                    f,
+                    "monkey {} throws to monkey {}, which does not exist",
+                    monkey, target
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+/// How worry levels are kept manageable after each inspection.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum)]
+pub enum Relief {
+    /// Divide by three, rounding down
+    #[value(name = "div3")]
+    DivideBy3,
+    /// Keep worry levels modulo the LCM of every monkey's divisor
+    #[value(name = "lcm")]
+    ModuloLcm,
+    /// Keep exact worry levels as big integers
+    None,
+}
+
+#[derive(Clone, Debug)]
+pub struct Monkey {
+    _id: u32,
+    items: VecDeque<u64>,
+    inspections: u64,
+    operation: Expr,
+    divisor: u64,
+    mt: usize,
+    mf: usize,
+}
+
+fn monkey(input: &str) -> IResult<'_, Vec<Monkey>> {
+    separated_list1(
+        tag("\n\n"),
+        map(
+            preceded(
+                tag("Monkey "),
+                cut(tuple((
+                    u32,
+                    preceded(
+                        tag(":\n  Starting items: "),
+                        separated_list1(tag(", "), u64),
+                    ),
+                    preceded(tag("\n  Operation: new = "), expr::expr),
+                    preceded(tag("\n  Test: divisible by "), u64),
+                    preceded(tag("\n    If true: throw to monkey "), u32),
+                    preceded(tag("\n    If false: throw to monkey "), u32),
+                ))),
+            ),
+            |(id, items, operation, divisor, mt, mf)| Monkey {
+                _id: id,
+                items: items.into(),
+                inspections: 0,
+                operation,
+                divisor,
+                mt: mt as usize,
+                mf: mf as usize,
+            },
+        ),
+    )(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Monkey>, Error> {
+    let monkeys = parse_all(input, monkey)?;
+    if let Some(monkey) = monkeys.iter().position(|monkey| monkey.divisor == 0) {
+        return Err(Error::ZeroDivisor { monkey });
+    }
+    for (monkey, Monkey { mt, mf, .. }) in monkeys.iter().enumerate() {
+        if let Some(&target) = [mt, mf]
+            .into_iter()
+            .find(|&&target| target >= monkeys.len())
+        {
+            return Err(Error::UnknownTarget { monkey, target });
+        }
+    }
+    Ok(monkeys)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(monkeys: &[Monkey]) -> u64 {
+    monkeys.iter().fold(1, |lcm, Monkey { divisor, .. }| {
+        lcm / gcd(lcm, *divisor) * divisor
+    })
+}
+
+fn check_relief(monkeys: &[Monkey], relief: Relief) -> Result<(), Error> {
+    if relief == Relief::ModuloLcm {
+        if let Some(monkey) = monkeys.iter().position(|m| m.operation.divides()) {
+            return Err(Error::Relief { monkey, relief });
+        }
+    }
+    Ok(())
+}
+
+/// The product of the two highest inspection counts after `rounds` rounds.
+/// Under modulo-LCM relief the rounds are extrapolated rather than simulated,
+/// so `rounds` can run to the trillions.
+pub fn monkey_business(monkeys: &[Monkey], rounds: u64, relief: Relief) -> Result<u128, Error> {
+    match relief {
+        Relief::None => inspections::<BigUint>(monkeys, rounds, relief),
+        Relief::DivideBy3 => inspections::<u64>(monkeys, rounds, relief),
+        Relief::ModuloLcm => extrapolate(monkeys, rounds).map(|(inspections, _)| inspections),
+    }
+    .map(|mut inspections| {
+        inspections.sort_unstable();
+        inspections
+            .iter()
+            .rev()
+            .take(2)
+            .map(|&n| n as u128)
+            .product()
+    })
+}
+
+fn inspections<T: Value>(
+    monkeys: &[Monkey],
+    rounds: u64,
+    relief: Relief,
+) -> Result<Vec<u64>, Error> {
+    simulate::<T>(monkeys, rounds, relief, None)
+}
+
+/// Plays `rounds` rounds, returning each monkey's inspection count and
+/// recording every round in `history` if given. Items are numbered in the
+/// order the notes list them.
+fn simulate<T: Value>(
+    monkeys: &[Monkey],
+    rounds: u64,
+    relief: Relief,
+    mut history: Option<&mut History>,
+) -> Result<Vec<u64>, Error> {
+    check_relief(monkeys, relief)?;
+    let mut ids = 0..;
+    let mut items: Vec<VecDeque<(usize, T)>> = monkeys
+        .iter()
+        .map(|Monkey { items, .. }| {
+            items
+                .iter()
+                .map(|&item| (ids.next().unwrap(), T::num(item)))
+                .collect()
+        })
+        .collect();
+    let mut inspections: Vec<u64> = monkeys
+        .iter()
+        .map(|Monkey { inspections, .. }| *inspections)
+        .collect();
+    let mut next_items: HashMap<usize, VecDeque<(usize, T)>> = HashMap::new();
+    let lcm = lcm(monkeys);
+
+    for _round in 0..rounds {
+        let before = inspections.clone();
+        for (i, monkey) in monkeys.iter().enumerate() {
+            let eval = |worry: &T| match relief {
+                Relief::DivideBy3 => T::apply(Op::Div, monkey.operation.eval(worry)?, T::num(3)),
+                Relief::ModuloLcm => monkey.operation.eval_mod(worry, lcm),
+                Relief::None => monkey.operation.eval(worry),
+            };
+            while let Some((id, item)) = items[i].pop_front() {
+                let worry = eval(&item).map_err(|err| Error::Eval { monkey: i, err })?;
+                let test = worry.divisible_by(monkey.divisor);
+                let next = if test { monkey.mt } else { monkey.mf };
+                next_items.entry(next).or_default().push_back((id, worry));
+                inspections[i] += 1;
+                if let Some(history) = history.as_deref_mut() {
+                    history.throws[i][usize::from(!test)] += 1;
+                }
+            }
+            next_items.iter_mut().for_each(|(&i, thrown)| {
+                if let Some(items) = items.get_mut(i) {
+                    items.append(thrown);
+                }
+            });
+        }
+        if let Some(history) = history.as_deref_mut() {
+            history.rounds.push(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, held)| Turn {
+                        inspections: inspections[i] - before[i],
+                        items: held.iter().map(|&(id, _)| id).collect(),
+                    })
+                    .collect(),
+            );
+        }
+    }
+    Ok(inspections)
+}
+
+/// Plays `rounds` rounds while recording what happens in each of them.
+pub fn record(monkeys: &[Monkey], rounds: u64, relief: Relief) -> Result<History, Error> {
+    let mut history = History::new(monkeys.len());
+    match relief {
+        Relief::None => simulate::<BigUint>(monkeys, rounds, relief, Some(&mut history)),
+        _ => simulate::<u64>(monkeys, rounds, relief, Some(&mut history)),
+    }?;
+    Ok(history)
+}
+
+/// An item's journey under modulo-LCM relief: from round `start` onwards,
+/// it is back with the same monkey and worry level every `length` rounds.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Cycle {
+    pub monkey: usize,
+    pub worry: u64,
+    pub start: u64,
+    pub length: u64,
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "item {} at monkey {} repeats every {} rounds from round {}",
+            self.worry, self.monkey, self.length, self.start
+        )
+    }
+}
+
+/// Follows one item until its state at the start of a round repeats,
+/// returning the cycle and how often each monkey has inspected it by the
+/// start of every round so far.
+fn journey(
+    monkeys: &[Monkey],
+    lcm: u64,
+    monkey: usize,
+    worry: u64,
+) -> Result<(Cycle, Vec<Vec<u64>>), Error> {
+    let mut seen: HashMap<(usize, u64), u64> = HashMap::new();
+    let mut counts = vec![vec![0; monkeys.len()]];
+    let (mut at, mut level) = (monkey, worry);
+    loop {
+        let round = counts.len() as u64 - 1;
+        if let Some(&start) = seen.get(&(at, level)) {
+            let cycle = Cycle {
+                monkey,
+                worry,
+                start,
+                length: round - start,
+            };
+            return Ok((cycle, counts));
+        }
+        seen.insert((at, level), round);
+        let mut inspected = counts[counts.len() - 1].clone();
+        // Items thrown to a later monkey are inspected again this round.
+        loop {
+            let thrower = &monkeys[at];
+            inspected[at] += 1;
+            level = thrower
+                .operation
+                .eval_mod(&level, lcm)
+                .map_err(|err| Error::Eval { monkey: at, err })?;
+            let next = if level.divisible_by(thrower.divisor) {
+                thrower.mt
+            } else {
+                thrower.mf
+            };
+            let done = next <= at;
+            at = next;
+            if done {
+                break;
+            }
+        }
+        counts.push(inspected);
+    }
+}
+
+/// Each monkey's inspection count after `rounds` rounds under modulo-LCM
+/// relief, found by following every item until it cycles, along with the
+/// cycle of each item in the order the notes list them.
+pub fn extrapolate(monkeys: &[Monkey], rounds: u64) -> Result<(Vec<u64>, Vec<Cycle>), Error> {
+    check_relief(monkeys, Relief::ModuloLcm)?;
+    let lcm = lcm(monkeys);
+    let mut inspections = vec![0; monkeys.len()];
+    let mut cycles = Vec::new();
+    for (monkey, Monkey { items, .. }) in monkeys.iter().enumerate() {
+        for &worry in items {
+            let (cycle, counts) = journey(monkeys, lcm, monkey, worry)?;
+            let at = |round: u64| &counts[round as usize];
+            let (repeats, round) = if rounds <= cycle.start {
+                (0, rounds)
+            } else {
+                let offset = rounds - cycle.start;
+                (offset / cycle.length, cycle.start + offset % cycle.length)
+            };
+            let start = at(cycle.start);
+            let end = at(cycle.start + cycle.length);
+            for (i, total) in inspections.iter_mut().enumerate() {
+                *total += at(round)[i] + repeats * (end[i] - start[i]);
+            }
+            cycles.push(cycle);
+        }
+    }
+    Ok((inspections, cycles))
+}
+
+pub fn part1(monkeys: &[Monkey]) -> Result<u128, Error> {
+    monkey_business(monkeys, 20, Relief::DivideBy3)
+}
+
+pub fn part2(monkeys: &[Monkey]) -> Result<u128, Error> {
+    monkey_business(monkeys, 10000, Relief::ModuloLcm)
+}
+
+pub fn solve(input: &str) -> Result<(u128, u128), Error> {
+    let monkeys = parse(input)?;
+    Ok((part1(&monkeys)?, part2(&monkeys)?))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let monkeys = parse(input)?;
+    Ok(match part {
+        Part::One => part1(&monkeys)?.to_string(),
+        Part::Two => part2(&monkeys)?.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{extrapolate, inspections, monkey_business, parse, part1, part2, record, Relief};
+
+    const EXAMPLE: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    #[test]
+    fn example() {
+        let monkeys = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&monkeys).unwrap(), 10605);
+        assert_eq!(part2(&monkeys).unwrap(), 2713310158);
+        assert_eq!(
+            monkey_business(&monkeys, 20, Relief::None).unwrap(),
+            monkey_business(&monkeys, 20, Relief::ModuloLcm).unwrap()
+        );
+    }
+
+    #[test]
+    fn extrapolation_matches_simulation() {
+        let monkeys = parse(EXAMPLE).unwrap();
+        for rounds in [0, 1, 20, 1000, 10000] {
+            assert_eq!(
+                extrapolate(&monkeys, rounds).unwrap().0,
+                inspections::<u64>(&monkeys, rounds, Relief::ModuloLcm).unwrap()
+            );
+        }
+        let (_, cycles) = extrapolate(&monkeys, 0).unwrap();
+        assert_eq!(cycles.len(), 10);
+        assert!(cycles.iter().all(|cycle| cycle.length > 0));
+    }
+
+    #[test]
+    fn records_history() {
+        let monkeys = parse(EXAMPLE).unwrap();
+        let history = record(&monkeys, 20, Relief::DivideBy3).unwrap();
+        let first = &history.rounds[0];
+        let inspected: Vec<u64> = first.iter().map(|turn| turn.inspections).collect();
+        let held: Vec<usize> = first.iter().map(|turn| turn.items.len()).collect();
+        assert_eq!((inspected, held), (vec![2, 4, 3, 5], vec![4, 6, 0, 0]));
+        let throws: Vec<u64> = history.throws.iter().map(|[t, f]| t + f).collect();
+        assert_eq!(throws, vec![101, 95, 7, 105]);
+        let csv = history.csv();
+        assert_eq!(csv.lines().count(), 1 + 20 * 4);
+        assert!(csv.ends_with("\n20,3,6,105,0,"));
+        assert!(history
+            .dot(&monkeys)
+            .contains("0 -> 3 [label=\"false: 101\", penwidth=5.00"));
+    }
+
+    fn pair(operation: &str, divisors: [u64; 2]) -> String {
+        format!(
+            "\
+Monkey 0:
+  Starting items: 2, 10
+  Operation: new = {}
+  Test: divisible by {}
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 5
+  Operation: new = old + 1
+  Test: divisible by {}
+    If true: throw to monkey 0
+    If false: throw to monkey 0",
+            operation, divisors[0], divisors[1]
+        )
+    }
+
+    #[test]
+    fn lcm_relief_reduces_each_operation() {
+        for (operation, divisors) in [
+            ("old * 7 - 5", [3, 2]),
+            ("old * old * old", [1000003, 1000033]),
+        ] {
+            let monkeys = parse(&pair(operation, divisors)).unwrap();
+            assert_eq!(
+                monkey_business(&monkeys, 8, Relief::None).unwrap(),
+                monkey_business(&monkeys, 8, Relief::ModuloLcm).unwrap()
+            );
+            assert_eq!(
+                extrapolate(&monkeys, 10000).unwrap().0,
+                inspections::<u64>(&monkeys, 10000, Relief::ModuloLcm).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_a_zero_divisor() {
+        let notes = EXAMPLE.replace("divisible by 13", "divisible by 0");
+        assert_eq!(
+            parse(&notes).unwrap_err().to_string(),
+            "monkey 2 tests for divisibility by zero"
+        );
+    }
+
+    #[test]
+    fn rejects_throws_to_missing_monkeys() {
+        let notes = EXAMPLE.replace("If false: throw to monkey 0", "If false: throw to monkey 7");
+        assert_eq!(
+            parse(&notes).unwrap_err().to_string(),
+            "monkey 1 throws to monkey 7, which does not exist"
+        );
+    }
+
+    #[test]
+    fn lcm_relief_rejects_division() {
+        let monkeys = parse(&EXAMPLE.replace("old + 6", "(old + 6) / 2")).unwrap();
+        assert_eq!(
+            monkey_business(&monkeys, 20, Relief::ModuloLcm)
+                .unwrap_err()
+                .to_string(),
+            "monkey 1 divides its worry level, which lcm relief cannot keep track of"
+        );
+        assert!(monkey_business(&monkeys, 20, Relief::None).is_ok());
+    }
+}
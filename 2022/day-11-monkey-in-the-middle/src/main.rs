@@ -1,162 +1,89 @@
+use aoc_core::{Input, Output, Part};
 use clap::Parser;
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{anychar, digit1},
-    combinator::map,
-    multi::separated_list1,
-    sequence::{preceded, separated_pair, tuple},
-    IResult,
-};
-use std::{
-    collections::{HashMap, VecDeque},
-    io,
+use day_11_monkey_in_the_middle::{
+    extrapolate, monkey_business, parse, record, solve, solve_part, Relief,
 };
+use std::{fs, path::PathBuf};
 
 #[derive(Parser)]
 struct Args {
     #[arg(long, default_value_t = false)]
     worry_big: bool,
-}
-
-#[derive(Debug)]
-enum Operation {
-    Add,
-    Mul,
-}
-
-#[derive(Debug)]
-enum Operand {
-    Num(u64),
-    Old,
-}
-
-#[derive(Debug)]
-struct Monkey {
-    _id: u32,
-    items: VecDeque<u64>,
-    inspections: u64,
-    operation: Operation,
-    operand: Operand,
-    divisor: u64,
-    mt: usize,
-    mf: usize,
-}
-
-fn monkey(input: &str) -> IResult<&str, Vec<Monkey>> {
-    map(
-        separated_list1(
-            tag("\n\n"),
-            tuple((
-                preceded(tag("Monkey "), digit1),
-                preceded(
-                    tag(":\n  Starting items: "),
-                    separated_list1(tag(", "), digit1),
-                ),
-                preceded(
-                    tag("\n  Operation: new = old "),
-                    separated_pair(anychar, tag(" "), alt((digit1, tag("old")))),
-                ),
-                preceded(tag("\n  Test: divisible by "), digit1),
-                preceded(tag("\n    If true: throw to monkey "), digit1),
-                preceded(tag("\n    If false: throw to monkey "), digit1),
-            )),
-        ),
-        |monkeys| {
-            monkeys
-                .iter()
-                .map(
-                    |(id, items, (operation, operand), divisor, mt, mf): &(
-                        &str,
-                        Vec<&str>,
-                        (char, &str),
-                        &str,
-                        &str,
-                        &str,
-                    )| Monkey {
-                        _id: id.parse().unwrap(),
-                        items: items.iter().map(|&i| i.parse().unwrap()).collect(),
-                        inspections: 0,
-                        operation: match operation {
-                            '+' => Operation::Add,
-                            '*' => Operation::Mul,
-                            _ => panic!("unknown operation: {}", operation),
-                        },
-                        operand: match *operand {
-                            "old" => Operand::Old,
-                            num => Operand::Num(num.parse().unwrap()),
-                        },
-                        divisor: divisor.parse().unwrap(),
-                        mt: mt.parse().unwrap(),
-                        mf: mf.parse().unwrap(),
-                    },
-                )
-                .collect()
-        },
-    )(input)
+    /// Keep worry levels down this way instead of the part's own
+    #[arg(long, value_enum)]
+    relief: Option<Relief>,
+    /// Play this many rounds instead of the part's own; counts in the
+    /// trillions need modulo-LCM relief
+    #[arg(long)]
+    rounds: Option<u64>,
+    /// Report how often each item's journey repeats under modulo-LCM relief
+    #[arg(long, default_value_t = false, conflicts_with = "relief")]
+    cycles: bool,
+    /// Write each monkey's inspections and items after every round as CSV
+    #[arg(long, value_name = "PATH")]
+    csv: Option<PathBuf>,
+    /// Write who throws to whom, weighted by throw counts, as Graphviz DOT
+    #[arg(long, value_name = "PATH")]
+    dot: Option<PathBuf>,
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let Args { worry_big } = Args::parse();
-    let input = io::stdin()
-        .lines()
-        .map(Result::unwrap)
-        .collect::<Vec<String>>()
-        .join("\n");
-    let mut monkeys = monkey(&input).map(|(_, monkeys)| monkeys).unwrap();
-    let mut next_items: HashMap<usize, VecDeque<u64>> = HashMap::new();
-    let num_rounds = if worry_big { 10000 } else { 20 };
-    let common_divisor: u64 = monkeys
-        .iter()
-        .map(|Monkey { divisor, .. }| *divisor)
-        .product();
-
-    (0..num_rounds).for_each(|_round| {
-        (0..monkeys.len()).for_each(|i| {
-            if let Some(Monkey {
-                items,
-                operation,
-                operand,
-                divisor,
-                mf,
-                mt,
-                inspections,
-                ..
-            }) = monkeys.get_mut(i)
-            {
-                while let Some(item) = items.pop_front() {
-                    let mut worry = match (&operation, &operand) {
-                        (Operation::Add, Operand::Num(num)) => item + num,
-                        (Operation::Add, Operand::Old) => item + item,
-                        (Operation::Mul, Operand::Num(num)) => item * num,
-                        (Operation::Mul, Operand::Old) => item * item,
-                    };
-                    if worry_big {
-                        worry %= common_divisor;
-                    } else {
-                        worry /= 3;
-                    }
-                    let next = if worry % *divisor == 0 { *mt } else { *mf };
-                    next_items
-                        .entry(next)
-                        .or_insert_with(VecDeque::new)
-                        .push_back(worry);
-                    *inspections += 1;
-                }
+    let Args {
+        worry_big,
+        relief,
+        rounds,
+        cycles,
+        csv,
+        dot,
+        input,
+        output,
+    } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(env!("CARGO_MANIFEST_DIR"), &input, &Part::ALL, solve_part) {
+        return;
+    }
+    if cycles || relief.is_some() || rounds.is_some() || csv.is_some() || dot.is_some() {
+        let monkeys = parse(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        let (relief, rounds) = match (cycles || worry_big, relief) {
+            (false, relief) => (relief.unwrap_or(Relief::DivideBy3), rounds.unwrap_or(20)),
+            (true, relief) => (relief.unwrap_or(Relief::ModuloLcm), rounds.unwrap_or(10000)),
+        };
+        if cycles {
+            let (_, cycles) =
+                extrapolate(&monkeys, rounds).unwrap_or_else(|err| aoc_core::fail(err));
+            for cycle in cycles {
+                println!("{}", cycle);
             }
-            next_items.iter_mut().for_each(|(&i, thrown)| {
-                if let Some(Monkey { items, .. }) = monkeys.get_mut(i) {
-                    items.append(thrown);
-                }
-            });
-        });
-    });
-
-    let mut inspections: Vec<u64> = monkeys
-        .iter()
-        .map(|Monkey { inspections, .. }| *inspections)
-        .collect();
-    inspections.sort_unstable();
-    let result: u64 = inspections.iter().rev().take(2).product();
-    println!("{}", result);
+        }
+        if csv.is_some() || dot.is_some() {
+            let history =
+                record(&monkeys, rounds, relief).unwrap_or_else(|err| aoc_core::fail(err));
+            let write = |path: PathBuf, contents: String| {
+                fs::write(&path, contents + "\n").unwrap_or_else(|err| {
+                    aoc_core::fail(format!("cannot write {}: {}", path.display(), err))
+                })
+            };
+            if let Some(path) = csv {
+                write(path, history.csv());
+            }
+            if let Some(path) = dot {
+                write(path, history.dot(&monkeys));
+            }
+        }
+        let answer =
+            monkey_business(&monkeys, rounds, relief).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", answer);
+    } else if worry_big {
+        let answer = solve_part(&input, Part::Two).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", answer);
+    } else {
+        let (part1, part2) = solve(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        aoc_core::print_answers(part1, part2);
+    }
 }
@@ -0,0 +1,353 @@
+use aoc_core::Part;
+use aoc_grid::{Direction, Grid, ParseGridError, Point};
+use aoc_path::{astar, bfs, dijkstra_reach, Path};
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Map(ParseGridError),
+    Missing { marker: char },
+    Unreachable,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Map(ParseGridError::InvalidCell { x, y, c }) => write!(
+                f,
+                "invalid heightmap at line {}, column {}: expected a-z, S or E, found {:?}",
+                y + 1,
+                x + 1,
+                c
+            ),
+            Error::Map(err) => write!(f, "invalid heightmap at {}", err),
+            Error::Missing { marker } => write!(f, "heightmap has no {:?} square", marker),
+            Error::Unreachable => write!(f, "the best signal cannot be reached"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn find_points(grid: &Grid<char>, target: char) -> Vec<Point> {
+    grid.iter()
+        .filter_map(|(p, &c)| if c == target { Some(p) } else { None })
+        .collect()
+}
+
+fn find_and_replace_point(grid: &mut Grid<char>, target: char, replacement: char) -> Option<Point> {
+    let point = grid.find(|&c| c == target)?;
+    grid[point] = replacement;
+
+    Some(point)
+}
+
+/// What a step costs.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, ValueEnum)]
+pub enum Cost {
+    /// One per step
+    #[default]
+    Unit,
+    /// One per step plus the change in elevation, up or down
+    Change,
+    /// One per step plus the elevation climbed
+    Ascent,
+}
+
+/// Which steps the climber may take, and what they cost.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Args)]
+pub struct Rules {
+    /// The most the elevation may rise in one step
+    #[arg(long, default_value_t = 1)]
+    pub max_ascent: u32,
+    /// The most the elevation may fall in one step, if limited
+    #[arg(long)]
+    pub max_descent: Option<u32>,
+    /// Allow diagonal steps
+    #[arg(long, default_value_t = false)]
+    pub diagonal: bool,
+    /// How steps are weighted
+    #[arg(long, value_enum, default_value_t = Cost::Unit)]
+    pub cost: Cost,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            max_ascent: 1,
+            max_descent: None,
+            diagonal: false,
+            cost: Cost::Unit,
+        }
+    }
+}
+
+impl Rules {
+    fn allows(&self, current: char, next: char) -> bool {
+        let rise = next as i64 - current as i64;
+        rise <= self.max_ascent as i64
+            && self
+                .max_descent
+                .is_none_or(|descent| -rise <= descent as i64)
+    }
+
+    fn cost(&self, current: char, next: char) -> usize {
+        let rise = next as i64 - current as i64;
+        1 + match self.cost {
+            Cost::Unit => 0,
+            Cost::Change => rise.unsigned_abs() as usize,
+            Cost::Ascent => rise.max(0) as usize,
+        }
+    }
+}
+
+fn find_moves<'a>(
+    grid: &'a Grid<char>,
+    point: Point,
+    rules: &'a Rules,
+) -> impl Iterator<Item = Point> + 'a {
+    let current = grid[point];
+    let neighbours: Vec<Point> = if rules.diagonal {
+        grid.neighbours8(point).collect()
+    } else {
+        grid.neighbours4(point).collect()
+    };
+    neighbours
+        .into_iter()
+        .filter(move |&next| rules.allows(current, grid[next]))
+}
+
+pub struct Heightmap {
+    map: Grid<char>,
+    start: Point,
+    end: Point,
+}
+
+pub fn parse(input: &str) -> Result<Heightmap, Error> {
+    let mut map: Grid<char> = Grid::parse_with(input, |c| match c {
+        'a'..='z' | 'S' | 'E' => Ok(c),
+        _ => Err(()),
+    })
+    .map_err(Error::Map)?;
+    let start = find_and_replace_point(&mut map, 'S', 'a').ok_or(Error::Missing { marker: 'S' })?;
+    let end = find_and_replace_point(&mut map, 'E', 'z').ok_or(Error::Missing { marker: 'E' })?;
+
+    Ok(Heightmap { map, start, end })
+}
+
+fn potential_start(heightmap: &Heightmap, part: Part) -> Vec<Point> {
+    match part {
+        Part::One => vec![heightmap.start],
+        Part::Two => find_points(&heightmap.map, 'a'),
+    }
+}
+
+fn moves<'a>(
+    heightmap: &'a Heightmap,
+    rules: &'a Rules,
+) -> impl FnMut(&Point) -> Vec<(Point, usize)> + 'a {
+    |&point| {
+        let current = heightmap.map[point];
+        find_moves(&heightmap.map, point, rules)
+            .map(|next| (next, rules.cost(current, heightmap.map[next])))
+            .collect()
+    }
+}
+
+/// The cheapest route to the best signal, setting out from the marked start
+/// or, for part two, from whichever square of elevation `a` is closest.
+pub fn route(
+    heightmap: &Heightmap,
+    part: Part,
+    rules: &Rules,
+) -> Result<Path<Point, usize>, Error> {
+    let starts = potential_start(heightmap, part);
+    let goal = |&point: &Point| point == heightmap.end;
+    match rules.cost {
+        Cost::Unit => bfs(
+            starts,
+            |&point| find_moves(&heightmap.map, point, rules).collect::<Vec<_>>(),
+            goal,
+        ),
+        // Every step costs at least one, so the number of steps left is a
+        // lower bound on the cost.
+        _ => astar(
+            starts,
+            moves(heightmap, rules),
+            |point| match rules.diagonal {
+                true => point.chebyshev(&heightmap.end) as usize,
+                false => point.manhattan(&heightmap.end) as usize,
+            },
+            goal,
+        ),
+    }
+    .ok_or(Error::Unreachable)
+}
+
+pub fn part1(heightmap: &Heightmap) -> Result<usize, Error> {
+    Ok(route(heightmap, Part::One, &Rules::default())?.cost)
+}
+
+pub fn part2(heightmap: &Heightmap) -> Result<usize, Error> {
+    Ok(route(heightmap, Part::Two, &Rules::default())?.cost)
+}
+
+pub fn solve(input: &str) -> Result<(usize, usize), Error> {
+    let heightmap = parse(input)?;
+    Ok((part1(&heightmap)?, part2(&heightmap)?))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let heightmap = parse(input)?;
+    let result = match part {
+        Part::One => part1(&heightmap)?,
+        Part::Two => part2(&heightmap)?,
+    };
+
+    Ok(result.to_string())
+}
+
+/// How [`visualise`] draws the map.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct Style {
+    /// Plain characters only, for terminals without truecolor.
+    pub ascii: bool,
+    /// Mark the square the route sets out from.
+    pub highlight_start: bool,
+}
+
+fn arrow(direction: Direction) -> char {
+    match direction {
+        Direction::N => '^',
+        Direction::E => '>',
+        Direction::S => 'v',
+        Direction::W => '<',
+        Direction::NE | Direction::SW => '/',
+        Direction::SE | Direction::NW => '\\',
+    }
+}
+
+/// Draws the heightmap with the route to the best signal as arrows. In
+/// colour, every reachable square is tinted by its cost from the start.
+pub fn visualise(input: &str, part: Part, rules: &Rules, style: Style) -> Result<String, Error> {
+    let heightmap = parse(input)?;
+    let route = route(&heightmap, part, rules)?;
+    let visited = dijkstra_reach(potential_start(&heightmap, part), moves(&heightmap, rules));
+    let colors: [(u8, u8, u8); 6] = [
+        (127, 0, 127),
+        (0, 0, 255),
+        (0, 255, 0),
+        (255, 255, 0),
+        (255, 127, 0),
+        (255, 0, 0),
+    ];
+    let mut cells: Grid<Option<char>> =
+        Grid::new(heightmap.map.width(), heightmap.map.height(), None);
+    for (&from, &to) in route.nodes.iter().zip(&route.nodes[1..]) {
+        cells[from] = Direction::ALL
+            .into_iter()
+            .find(|&direction| from + direction == to)
+            .map(arrow);
+    }
+    cells[heightmap.end] = Some('E');
+    let start = *route.start();
+    if style.highlight_start && style.ascii {
+        cells[start] = Some('S');
+    }
+    Ok(heightmap
+        .map
+        .rows()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, &c)| {
+                    let point = Point::new(x as i64, y as i64);
+                    let on_route = cells[point];
+                    let cell = on_route.unwrap_or(c).to_string();
+                    if style.ascii {
+                        return cell;
+                    }
+                    let cell = match (on_route, visited.cost(&point)) {
+                        (Some(_), _) => cell.bold().white(),
+                        (None, Some(distance)) => {
+                            let (r, g, b) = colors[distance % 6];
+                            cell.truecolor(r, g, b)
+                        }
+                        (None, None) => cell.normal(),
+                    };
+                    if style.highlight_start && point == start {
+                        cell.on_red().to_string()
+                    } else {
+                        cell.to_string()
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, route, solve, visualise, Cost, Rules, Style};
+    use aoc_core::Part;
+
+    const EXAMPLE: &str = "\
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn solves_example() {
+        assert_eq!(solve(EXAMPLE).unwrap(), (31, 29));
+    }
+
+    #[test]
+    fn follows_configured_rules() {
+        let heightmap = parse(EXAMPLE).unwrap();
+        let cost = |rules: Rules| route(&heightmap, Part::One, &rules).map(|path| path.cost);
+        let diagonal = Rules {
+            diagonal: true,
+            ..Rules::default()
+        };
+        assert!(cost(diagonal).unwrap() < 31);
+        let steep = Rules {
+            max_ascent: 25,
+            ..Rules::default()
+        };
+        assert_eq!(cost(steep).unwrap(), 2 + 5);
+        let weighted = Rules {
+            cost: Cost::Ascent,
+            ..Rules::default()
+        };
+        assert_eq!(cost(weighted).unwrap(), 31 + 25);
+        let flat = Rules {
+            max_ascent: 0,
+            max_descent: Some(0),
+            ..Rules::default()
+        };
+        assert!(cost(flat).is_err());
+    }
+
+    #[test]
+    fn draws_the_route() {
+        let style = Style {
+            ascii: true,
+            highlight_start: true,
+        };
+        assert_eq!(
+            visualise(EXAMPLE, Part::Two, &Rules::default(), style).unwrap(),
+            "\
+aabv<<<<
+abcvv<<^
+accv>E^^
+a>v>>>^^
+S^>>>>>^"
+        );
+    }
+}
@@ -1,6 +1,6 @@
+use aoc_core::{Input, Output, Part};
 use clap::Parser;
-use colored::Colorize;
-use std::{collections::HashMap, io};
+use day_12_hill_climbing_algorithm::{parse, route, solve, solve_part, visualise, Rules, Style};
 
 #[derive(Parser)]
 struct Args {
@@ -8,141 +8,58 @@ struct Args {
     any: bool,
     #[arg(long, default_value_t = false)]
     vis: bool,
-}
-
-#[derive(Eq, Hash, PartialEq, Clone, Copy, Debug)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-
-fn find_points(vec: &[Vec<char>], target: char) -> Vec<Point> {
-    vec.iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter().enumerate().filter_map(move |(x, c)| {
-                if *c == target {
-                    Some(Point { x, y })
-                } else {
-                    None
-                }
-            })
-        })
-        .collect()
-}
-
-fn find_and_replace_point(vec: &mut [Vec<char>], target: char, replacement: char) -> Option<Point> {
-    vec.iter_mut().enumerate().find_map(|(y, row)| {
-        row.iter_mut().enumerate().find_map(|(x, c)| {
-            if *c == target {
-                *c = replacement;
-
-                Some(Point { x, y })
-            } else {
-                None
-            }
-        })
-    })
-}
-
-fn one_lower_or_above(current: char, next: char) -> bool {
-    current as i32 - next as i32 <= 1
-}
-
-fn find_moves(
-    vec: &[Vec<char>],
-    &Point { x, y }: &Point,
-    predicate: fn(char, char) -> bool,
-) -> Vec<Point> {
-    let current = vec[y][x];
-    let mut result = Vec::new();
-    let can_move = |dx: i32, dy: i32| -> Option<Point> {
-        if y > 0 || dy >= 0 && x > 0 || dx >= 0 {
-            let y = (y as i32 + dy) as usize;
-            let x = (x as i32 + dx) as usize;
-            if let Some(&next) = vec.get(y).and_then(|row| row.get(x)) {
-                if predicate(current, next) {
-                    return Some(Point { x, y });
-                }
-            }
-        }
-
-        None
-    };
-    if let Some(point) = can_move(0, -1) {
-        result.push(point);
-    }
-    if let Some(point) = can_move(-1, 0) {
-        result.push(point);
-    }
-    if let Some(point) = can_move(1, 0) {
-        result.push(point);
-    }
-    if let Some(point) = can_move(0, 1) {
-        result.push(point);
-    }
-
-    result
+    /// Draw the map without colour
+    #[arg(long, default_value_t = false, requires = "vis")]
+    ascii: bool,
+    /// Mark the square the route sets out from
+    #[arg(long, default_value_t = false, requires = "vis")]
+    highlight_start: bool,
+    #[command(flatten)]
+    rules: Rules,
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let Args { any, vis } = Args::parse();
-    let mut input: Vec<Vec<char>> = io::stdin()
-        .lines()
-        .map(|line| line.unwrap().chars().collect())
-        .collect();
-    let start = find_and_replace_point(&mut input, 'S', 'a').unwrap();
-    let potential_start = if any {
-        find_points(&input, 'a')
-    } else {
-        vec![start]
-    };
-    let end = find_and_replace_point(&mut input, 'E', 'z').unwrap();
-    let mut visited: HashMap<Point, u32> = HashMap::new();
-    visited.insert(end, 0);
-
-    while potential_start
-        .iter()
-        .all(|point| visited.get(point).is_none())
-    {
-        let mut new_points: HashMap<Point, u32> = HashMap::new();
-        for (&point, &distance) in visited.iter() {
-            for next in find_moves(&input, &point, one_lower_or_above) {
-                new_points.insert(next, distance + 1);
-            }
-        }
-        for (&point, &distance) in new_points.iter() {
-            visited.entry(point).or_insert(distance);
-        }
+    let Args {
+        any,
+        vis,
+        ascii,
+        highlight_start,
+        rules,
+        input,
+        output,
+    } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(env!("CARGO_MANIFEST_DIR"), &input, &Part::ALL, solve_part) {
+        return;
     }
-
+    let part = if any { Part::Two } else { Part::One };
     if vis {
-        let colors: [(u8, u8, u8); 6] = [
-            (127, 0, 127),
-            (0, 0, 255),
-            (0, 255, 0),
-            (255, 255, 0),
-            (255, 127, 0),
-            (255, 0, 0),
-        ];
-        input.iter().enumerate().for_each(|(y, row)| {
-            row.iter().enumerate().for_each(|(x, c)| {
-                if let Some(distance) = visited.get(&Point { x, y }) {
-                    let (r, g, b) = colors[(distance % 6) as usize];
-                    print!("{}", c.to_string().truecolor(r, g, b));
-                } else {
-                    print!("{}", c);
-                }
-            });
-            println!();
-        });
+        let map = visualise(
+            &input,
+            part,
+            &rules,
+            Style {
+                ascii,
+                highlight_start,
+            },
+        )
+        .unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", map);
+    } else if rules != Rules::default() {
+        let heightmap = parse(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        let route = route(&heightmap, part, &rules).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", route.cost);
+    } else if any {
+        let answer = solve_part(&input, Part::Two).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", answer);
     } else {
-        let result: &u32 = potential_start
-            .iter()
-            .filter_map(|point| visited.get(point))
-            .min()
-            .unwrap();
-
-        println!("{}", result);
+        let (part1, part2) = solve(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        aoc_core::print_answers(part1, part2);
     }
 }
@@ -0,0 +1,22 @@
+#![no_main]
+
+use day_13_distress_signal::{compare, List};
+use libfuzzer_sys::fuzz_target;
+use std::cmp::Ordering;
+
+// Any packet the parser accepts must print back to text that parses to the
+// same packet, and compare equal to itself.
+fuzz_target!(|data: &str| {
+    if let Ok(list) = data.parse::<List<i32>>() {
+        let printed = list.to_string();
+        let parsed = printed.parse::<List<i32>>();
+        assert!(
+            parsed.as_ref().is_ok_and(|parsed| parsed.identical(&list)),
+            "{:?} printed as {:?}, which parses to {:?}",
+            list,
+            printed,
+            parsed
+        );
+        assert_eq!(compare(&list, &list), Ordering::Equal);
+    }
+});
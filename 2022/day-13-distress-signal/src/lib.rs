@@ -0,0 +1,166 @@
+use aoc_core::{
+    parse::{parse_all, IResult, ParseError},
+    Part,
+};
+use list::{list, wrap};
+use nom::{
+    character::complete::line_ending,
+    combinator::cut,
+    multi::{many1, separated_list0},
+    sequence::separated_pair,
+};
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+mod list;
+
+pub use list::{compare, difference, Difference, List, Reason};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid packet at {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+pub type ListPair<T> = (List<T>, List<T>);
+
+fn pairs<T: FromStr>(input: &str) -> IResult<'_, Vec<ListPair<T>>> {
+    separated_list0(
+        many1(line_ending),
+        cut(separated_pair(list, line_ending, list)),
+    )(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<ListPair<i32>>, Error> {
+    Ok(parse_all(input, pairs::<i32>)?)
+}
+
+pub fn part1(pairs: &[ListPair<i32>]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (left, right))| left < right)
+        .map(|(i, _)| i + 1)
+        .sum()
+}
+
+/// The puzzle's divider packets, `[[2]]` and `[[6]]`.
+pub fn default_dividers() -> Vec<List<i32>> {
+    vec![wrap(&wrap(&List::Atom(2))), wrap(&wrap(&List::Atom(6)))]
+}
+
+/// Every packet with the dividers added, in order, each paired with whether
+/// it is one of the dividers. A divider stays after any packets equal to it.
+pub fn sort_packets(pairs: &[ListPair<i32>], dividers: &[List<i32>]) -> Vec<(List<i32>, bool)> {
+    let mut all: Vec<(List<i32>, bool)> = pairs
+        .iter()
+        .flat_map(|(left, right)| [left, right])
+        .map(|packet| (packet.clone(), false))
+        .chain(dividers.iter().map(|divider| (divider.clone(), true)))
+        .collect();
+    all.sort();
+    all
+}
+
+/// The product of the dividers' one-based indices among the sorted packets.
+pub fn decoder_key(pairs: &[ListPair<i32>], dividers: &[List<i32>]) -> usize {
+    sort_packets(pairs, dividers)
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, divider))| *divider)
+        .map(|(i, _)| i + 1)
+        .product()
+}
+
+pub fn part2(pairs: &[ListPair<i32>]) -> usize {
+    decoder_key(pairs, &default_dividers())
+}
+
+/// One line per pair saying whether it is in order and what decided it.
+pub fn compare_pairs(pairs: &[ListPair<i32>]) -> String {
+    pairs
+        .iter()
+        .enumerate()
+        .map(|(i, (left, right))| match difference(left, right) {
+            Some(difference) if difference.ordering() == Ordering::Less => {
+                format!("pair {}: in order, {}", i + 1, difference)
+            }
+            Some(difference) => format!("pair {}: out of order, {}", i + 1, difference),
+            None => format!("pair {}: equal", i + 1),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn solve(input: &str) -> Result<(usize, usize), Error> {
+    let pairs = parse(input)?;
+    Ok((part1(&pairs), part2(&pairs)))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let pairs = parse(input)?;
+    let result = match part {
+        Part::One => part1(&pairs),
+        Part::Two => part2(&pairs),
+    };
+
+    Ok(result.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{compare_pairs, decoder_key, default_dividers, parse, part1, part2};
+
+    const EXAMPLE: &str = "\
+[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+    #[test]
+    fn example() {
+        let pairs = parse(EXAMPLE).unwrap();
+        assert_eq!((part1(&pairs), part2(&pairs)), (13, 140));
+        let mut dividers = default_dividers();
+        dividers.push("[]".parse().unwrap());
+        assert_eq!(decoder_key(&pairs, &dividers), 2 * 11 * 15);
+        let report = compare_pairs(&pairs);
+        assert_eq!(
+            report.lines().nth(2),
+            Some("pair 3: out of order, at [0][0]: 9 vs 8")
+        );
+    }
+}
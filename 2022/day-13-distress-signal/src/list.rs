@@ -0,0 +1,402 @@
+use aoc_core::parse::{parse_all, tag, IResult, ParseError};
+use nom::{
+    branch::alt,
+    bytes::complete::take_while1,
+    combinator::{cut, map, map_res},
+    multi::separated_list0,
+    sequence::{preceded, terminated},
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+/// A packet: an atom or a list of packets. Equality and ordering follow the
+/// puzzle's rules, under which an atom and the list holding just that atom
+/// are equal; [`List::identical`] tells them apart.
+#[derive(Clone, Debug)]
+pub enum List<T> {
+    List(Vec<List<T>>),
+    Atom(T),
+}
+
+impl<T> List<T> {
+    /// The atom this packet equals, if it is one or is lists nested around
+    /// just one.
+    fn as_atom(&self) -> Option<&T> {
+        match self {
+            List::Atom(atom) => Some(atom),
+            List::List(items) => match &items[..] {
+                [item] => item.as_atom(),
+                _ => None,
+            },
+        }
+    }
+
+    /// Whether two packets are written the same.
+    pub fn identical(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        match (self, other) {
+            (List::Atom(left), List::Atom(right)) => left == right,
+            (List::List(left), List::List(right)) => {
+                left.len() == right.len() && left.iter().zip(right).all(|(l, r)| l.identical(r))
+            }
+            _ => false,
+        }
+    }
+}
+
+pub(crate) fn wrap<T: Clone>(l: &List<T>) -> List<T> {
+    List::List(vec![l.clone()])
+}
+
+/// Orders two packets by the puzzle's rules; this is their `Ord`.
+pub fn compare<T: Clone + Ord>(left: &List<T>, right: &List<T>) -> Ordering {
+    match (left, right) {
+        (List::List(left), List::List(right)) => left
+            .iter()
+            .zip(right.iter())
+            .find_map(|(left, right)| {
+                let ord = compare(left, right);
+                if ord != Ordering::Equal {
+                    Some(ord)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_else(|| left.len().cmp(&right.len())),
+        (List::List(_), List::Atom(_)) => compare(left, &wrap(right)),
+        (List::Atom(_), List::List(_)) => compare(&wrap(left), right),
+        (List::Atom(left), List::Atom(right)) => left.cmp(right),
+    }
+}
+
+impl<T: Clone + Ord> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        compare(self, other) == Ordering::Equal
+    }
+}
+
+impl<T: Clone + Ord> Eq for List<T> {}
+
+impl<T: Clone + Ord> Ord for List<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare(self, other)
+    }
+}
+
+impl<T: Clone + Ord> PartialOrd for List<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(Ord::cmp(self, other))
+    }
+}
+
+// Hashes what is left once every list around a lone atom is dropped, which
+// is the same for any two packets that compare equal.
+impl<T: Hash> Hash for List<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match (self.as_atom(), self) {
+            (Some(atom), _) => {
+                state.write_u8(0);
+                atom.hash(state);
+            }
+            (None, List::List(items)) => {
+                state.write_u8(1);
+                items.len().hash(state);
+                items.iter().for_each(|item| item.hash(state));
+            }
+            (None, List::Atom(_)) => unreachable!("an atom is its own atom"),
+        }
+    }
+}
+
+/// Why two packets are in the order they are.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Reason<T> {
+    Atoms(T, T),
+    LeftRanOut,
+    RightRanOut,
+}
+
+/// Where two packets first differ: the index taken at each level of nesting
+/// on the way there, and what differs.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Difference<T> {
+    pub path: Vec<usize>,
+    pub reason: Reason<T>,
+}
+
+impl<T: Ord> Difference<T> {
+    pub fn ordering(&self) -> Ordering {
+        match &self.reason {
+            Reason::Atoms(left, right) => left.cmp(right),
+            Reason::LeftRanOut => Ordering::Less,
+            Reason::RightRanOut => Ordering::Greater,
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Difference<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "at the top level")?;
+        } else {
+            write!(f, "at ")?;
+            for i in &self.path {
+                write!(f, "[{}]", i)?;
+            }
+        }
+        match &self.reason {
+            Reason::Atoms(left, right) => write!(f, ": {} vs {}", left, right),
+            Reason::LeftRanOut => write!(f, ": left ran out of items"),
+            Reason::RightRanOut => write!(f, ": right ran out of items"),
+        }
+    }
+}
+
+/// Finds what decides the order of two packets, following the same rules
+/// as their `Ord`, or `None` if they compare equal. An atom compared with a
+/// list is treated as a list holding it, so the path steps into it at 0.
+pub fn difference<T: Clone + Ord>(left: &List<T>, right: &List<T>) -> Option<Difference<T>> {
+    match (left, right) {
+        (List::Atom(l), List::Atom(r)) => (l != r).then(|| Difference {
+            path: Vec::new(),
+            reason: Reason::Atoms(l.clone(), r.clone()),
+        }),
+        (List::List(_), List::Atom(_)) => difference(left, &wrap(right)),
+        (List::Atom(_), List::List(_)) => difference(&wrap(left), right),
+        (List::List(l), List::List(r)) => {
+            let found = l.iter().zip(r).enumerate().find_map(|(i, (l, r))| {
+                difference(l, r).map(|mut difference| {
+                    difference.path.insert(0, i);
+                    difference
+                })
+            });
+            found.or_else(|| {
+                let reason = match l.len().cmp(&r.len()) {
+                    Ordering::Less => Reason::LeftRanOut,
+                    Ordering::Greater => Reason::RightRanOut,
+                    Ordering::Equal => return None,
+                };
+                Some(Difference {
+                    path: Vec::new(),
+                    reason,
+                })
+            })
+        }
+    }
+}
+
+fn atom<T: FromStr>(input: &str) -> IResult<'_, List<T>> {
+    map_res(
+        take_while1(|c: char| !matches!(c, '[' | ']' | ',') && !c.is_whitespace()),
+        |atom: &str| atom.parse::<T>().map(List::Atom),
+    )(input)
+}
+
+pub(crate) fn list<T: FromStr>(input: &str) -> IResult<'_, List<T>> {
+    map(
+        preceded(
+            tag("["),
+            cut(terminated(
+                separated_list0(tag(","), alt((list, atom))),
+                tag("]"),
+            )),
+        ),
+        List::List,
+    )(input)
+}
+
+impl<T: FromStr> FromStr for List<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s, list)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            List::Atom(atom) => write!(f, "{}", atom),
+            List::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl<T: Into<Value>> From<List<T>> for Value {
+    fn from(list: List<T>) -> Self {
+        match list {
+            List::Atom(atom) => atom.into(),
+            List::List(items) => Value::Array(items.into_iter().map(Value::from).collect()),
+        }
+    }
+}
+
+/// Arrays become lists and anything else must deserialize as an atom.
+impl<T: DeserializeOwned> TryFrom<Value> for List<T> {
+    type Error = serde_json::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Array(items) => items
+                .into_iter()
+                .map(List::try_from)
+                .collect::<Result<_, _>>()
+                .map(List::List),
+            atom => serde_json::from_value(atom).map(List::Atom),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list::{difference, wrap, Difference, List, Reason};
+    use proptest::prelude::*;
+    use serde_json::{json, Value};
+    use std::{
+        cmp::Ordering,
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
+
+    fn hash(list: &List<i32>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        list.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn round_trips_through_text_and_json() {
+        for packet in ["[]", "[[]]", "[1,[2,[3,[4,[5,6,7]]]],8,9]", "[-3,[10]]"] {
+            let list: List<i32> = packet.parse().unwrap();
+            assert_eq!(list.to_string(), packet);
+            let value = Value::from(list.clone());
+            assert_eq!(value.to_string(), packet);
+            assert!(List::try_from(value).unwrap().identical(&list));
+        }
+        assert!("[1,[2]".parse::<List<i32>>().is_err());
+        assert!("[1,x]".parse::<List<i32>>().is_err());
+        assert!(List::<i32>::try_from(json!([1, "two"])).is_err());
+    }
+
+    #[test]
+    fn sorts_by_the_packet_order() {
+        let mut packets: Vec<List<u8>> = ["[[1],4]", "[1,1,3,1,1]", "[[]]", "[1,1,5,1,1]", "[]"]
+            .iter()
+            .map(|packet| packet.parse().unwrap())
+            .collect();
+        packets.sort();
+        let sorted: Vec<String> = packets.iter().map(List::to_string).collect();
+        assert_eq!(
+            sorted,
+            ["[]", "[[]]", "[1,1,3,1,1]", "[1,1,5,1,1]", "[[1],4]"]
+        );
+        assert_eq!(
+            "[[2]]".parse::<List<u8>>().unwrap().cmp(&List::Atom(2)),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn finds_the_deciding_difference() {
+        let pair = |left: &str, right: &str| {
+            difference::<i32>(&left.parse().unwrap(), &right.parse().unwrap())
+        };
+        let found = pair("[1,[2,[3,[4,[5,6,7]]]],8,9]", "[1,[2,[3,[4,[5,6,0]]]],8,9]").unwrap();
+        assert_eq!(
+            found,
+            Difference {
+                path: vec![1, 1, 1, 1, 2],
+                reason: Reason::Atoms(7, 0)
+            }
+        );
+        assert_eq!(found.to_string(), "at [1][1][1][1][2]: 7 vs 0");
+        let found = pair("[[1],[2,3,4]]", "[[1],4]").unwrap();
+        assert_eq!(
+            (found.path, found.reason),
+            (vec![1, 0], Reason::Atoms(2, 4))
+        );
+        let found = pair("[[[]]]", "[[]]").unwrap();
+        assert_eq!(found.to_string(), "at [0]: right ran out of items");
+        assert_eq!(pair("[[2]]", "[2]"), None);
+    }
+
+    fn packet() -> impl Strategy<Value = List<i32>> {
+        // Few distinct atoms, so that ties and promotions come up often.
+        let atom = (-2..3).prop_map(List::Atom);
+        atom.prop_recursive(4, 32, 4, |inner| {
+            prop::collection::vec(inner, 0..4).prop_map(List::List)
+        })
+    }
+
+    /// Collapses every list holding a single atom into that atom, so that
+    /// packets compare equal exactly when their normal forms are identical.
+    fn normalise(list: &List<i32>) -> List<i32> {
+        match list {
+            List::Atom(atom) => List::Atom(*atom),
+            List::List(items) => match &items.iter().map(normalise).collect::<Vec<_>>()[..] {
+                [atom @ List::Atom(_)] => atom.clone(),
+                items => List::List(items.to_vec()),
+            },
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn antisymmetric(a in packet(), b in packet()) {
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a.cmp(&a), Ordering::Equal);
+        }
+
+        #[test]
+        fn differences_decide_the_order(a in packet(), b in packet()) {
+            let ordering = difference(&a, &b).map_or(Ordering::Equal, |d| d.ordering());
+            prop_assert_eq!(ordering, a.cmp(&b));
+        }
+
+        #[test]
+        fn transitive(a in packet(), b in packet(), c in packet()) {
+            let mut sorted = [a, b, c];
+            sorted.sort();
+            prop_assert!(sorted[0] <= sorted[1] && sorted[1] <= sorted[2]);
+            prop_assert!(sorted[0] <= sorted[2]);
+        }
+
+        #[test]
+        fn equal_up_to_promotion(a in packet(), b in packet(), x in -2..3) {
+            let atom = List::Atom(x);
+            prop_assert_eq!(atom.cmp(&wrap(&wrap(&atom))), Ordering::Equal);
+            prop_assert_eq!(a.cmp(&b) == Ordering::Equal, normalise(&a).identical(&normalise(&b)));
+        }
+
+        #[test]
+        fn equal_packets_hash_alike(a in packet(), b in packet()) {
+            prop_assert_eq!(a == b, a.cmp(&b) == Ordering::Equal);
+            if a == b {
+                prop_assert_eq!(hash(&a), hash(&b));
+            }
+        }
+
+        #[test]
+        fn parses_what_it_displays(a in packet()) {
+            let list = List::List(vec![a]);
+            prop_assert!(list.to_string().parse::<List<i32>>().unwrap().identical(&list));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{cave::Cave, extent, simulate, Error, Event, Rules, Scan};
+use aoc_grid::Point;
+use serde_json::json;
+use std::io::{self, Write};
+
+/// How often an animation draws the cave.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Every {
+    /// Once this many more particles have settled.
+    Grains(usize),
+    /// At each step of each falling particle.
+    Step,
+}
+
+/// The width and height of the frames `animate` draws, counting the line
+/// under the cave that says how much has settled.
+pub fn frame_size(scan: &Scan, rules: &Rules, floor: bool) -> Result<(usize, usize), Error> {
+    let (min, max) = extent(scan, rules, floor)?;
+    let width = (max.x() - min.x() + 1) as usize;
+    let height = (max.y() - min.y() + 1) as usize + 1;
+    Ok((width, height))
+}
+
+/// Fills the cave as `pour` does, handing `frame` the cave drawn as often
+/// as `every` asks and once more at the end, and returns how many particles
+/// settled. A falling particle is drawn as `+`.
+pub fn animate(
+    scan: &Scan,
+    rules: &Rules,
+    floor: bool,
+    every: Every,
+    mut frame: impl FnMut(&str),
+) -> Result<usize, Error> {
+    let (min, max) = extent(scan, rules, floor)?;
+    let (width, _) = frame_size(scan, rules, floor)?;
+    let draw = |map: &Cave, falling: Option<Point>, settled| {
+        let mut text = map.render(min, max, rules.particle.symbol());
+        if let Some(p) = falling {
+            let i = (p.y() - min.y()) as usize * (width + 1) + (p.x() - min.x()) as usize;
+            text.replace_range(i..=i, "+");
+        }
+        text + &format!("\n{} units of {}", settled, rules.particle)
+    };
+    let mut settled = 0;
+    // Whether the last frame drawn shows the cave as it is now.
+    let mut drawn = false;
+    let (map, settled) = simulate(scan, rules, floor, |map, event| match (event, every) {
+        (Event::Falling(p), Every::Step) => {
+            frame(&draw(map, Some(p), settled));
+            drawn = false;
+        }
+        (Event::Falling(_), Every::Grains(_)) => {}
+        (Event::Settled(count), every) => {
+            settled = count;
+            drawn = every == Every::Step || matches!(every, Every::Grains(n) if count % n == 0);
+            if drawn {
+                frame(&draw(map, None, count));
+            }
+        }
+    })?;
+    if !drawn {
+        frame(&draw(&map, None, settled));
+    }
+    Ok(settled)
+}
+
+/// Turns a frame into terminal output that draws it over the last one,
+/// clearing the screen first if it is the first.
+pub fn ansi(frame: &str, first: bool) -> String {
+    let clear = if first { "\x1b[2J" } else { "" };
+    format!("{}\x1b[H{}\r\n", clear, frame.replace('\n', "\r\n"))
+}
+
+/// An asciicast v2 recording, with frames shown at a steady rate.
+pub struct Cast<W: Write> {
+    out: W,
+    fps: f64,
+    frames: usize,
+}
+
+impl<W: Write> Cast<W> {
+    pub fn new(mut out: W, (width, height): (usize, usize), fps: f64) -> io::Result<Self> {
+        writeln!(
+            out,
+            "{}",
+            json!({ "version": 2, "width": width, "height": height })
+        )?;
+        Ok(Cast {
+            out,
+            fps,
+            frames: 0,
+        })
+    }
+
+    pub fn frame(&mut self, frame: &str) -> io::Result<()> {
+        let time = self.frames as f64 / self.fps;
+        let output = ansi(frame, self.frames == 0);
+        writeln!(self.out, "{}", json!([time, "o", output]))?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{animate, parse, Cast, Every, Rules};
+    use serde_json::Value;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn draws_every_few_grains_and_the_end() {
+        let scan = parse(EXAMPLE).unwrap();
+        let mut frames = Vec::new();
+        let rules = Rules::default();
+        let sand = animate(&scan, &rules, false, Every::Grains(10), |frame| {
+            frames.push(frame.to_string())
+        })
+        .unwrap();
+        assert_eq!((sand, frames.len()), (24, 3));
+        assert!(frames[2].ends_with("#########.\n24 units of sand"));
+        let mut steps = 0;
+        animate(&scan, &rules, false, Every::Step, |frame| {
+            steps += usize::from(frame.contains('+'));
+        })
+        .unwrap();
+        assert!(steps > 24);
+    }
+
+    #[test]
+    fn records_an_asciicast() {
+        let scan = parse(EXAMPLE).unwrap();
+        let mut cast = Cast::new(Vec::new(), (10, 11), 4.0).unwrap();
+        animate(
+            &scan,
+            &Rules::default(),
+            false,
+            Every::Grains(12),
+            |frame| cast.frame(frame).unwrap(),
+        )
+        .unwrap();
+        let cast = String::from_utf8(cast.finish().unwrap()).unwrap();
+        let lines: Vec<Value> = cast
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[2][0], 0.25);
+        assert!(lines[1][2].as_str().unwrap().starts_with("\x1b[2J\x1b[H"));
+    }
+}
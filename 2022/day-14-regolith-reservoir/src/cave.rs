@@ -0,0 +1,109 @@
+use aoc_grid::Point;
+
+/// The cells particles can reach, stored as one bit per cell for what is
+/// filled and another for what of that is rock. Cells outside are empty,
+/// other than the floor.
+#[derive(Clone, Debug)]
+pub(crate) struct Cave {
+    min: Point,
+    width: usize,
+    height: usize,
+    /// Whether the bottom row is the cave floor.
+    floor: bool,
+    filled: Vec<u64>,
+    rock: Vec<u64>,
+}
+
+fn get(bits: &[u64], i: usize) -> bool {
+    bits[i / 64] & (1 << (i % 64)) != 0
+}
+
+fn set(bits: &mut [u64], i: usize) {
+    bits[i / 64] |= 1 << (i % 64);
+}
+
+impl Cave {
+    /// An empty cave spanning `min` to `max`, with rock along the bottom row
+    /// if it is to have a floor.
+    pub(crate) fn new(min: Point, max: Point, floor: bool) -> Self {
+        let width = (max.x() - min.x() + 1) as usize;
+        let height = (max.y() - min.y() + 1) as usize;
+        let words = (width * height).div_ceil(64);
+        let mut cave = Cave {
+            min,
+            width,
+            height,
+            floor,
+            filled: vec![0; words],
+            rock: vec![0; words],
+        };
+        if floor {
+            for x in min.x()..=max.x() {
+                cave.insert_rock(Point::new(x, max.y()));
+            }
+        }
+        cave
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        let x = usize::try_from(p.x() - self.min.x()).ok()?;
+        let y = usize::try_from(p.y() - self.min.y()).ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub(crate) fn contains(&self, p: Point) -> bool {
+        self.index(p).is_some()
+    }
+
+    /// Whether `p` holds rock or sand, counting the floor as going on past
+    /// the sides of the cave.
+    pub(crate) fn is_filled(&self, p: Point) -> bool {
+        match self.index(p) {
+            Some(i) => get(&self.filled, i),
+            None => self.floor && p.y() == self.min.y() + self.height as i64 - 1,
+        }
+    }
+
+    pub(crate) fn insert_rock(&mut self, p: Point) {
+        let i = self.index(p).expect("rock lies outside the cave");
+        set(&mut self.filled, i);
+        set(&mut self.rock, i);
+    }
+
+    pub(crate) fn insert_particle(&mut self, p: Point) {
+        let i = self.index(p).expect("particle settled outside the cave");
+        set(&mut self.filled, i);
+    }
+
+    /// The corners of the rock and particles, leaving out the floor.
+    pub(crate) fn bounds(&self) -> Option<(Point, Point)> {
+        let rows = self.height - usize::from(self.floor);
+        (0..rows * self.width)
+            .filter(|&i| get(&self.filled, i))
+            .map(|i| self.min + Point::new((i % self.width) as i64, (i / self.width) as i64))
+            .fold(None, |bounds, p| match bounds {
+                None => Some((p, p)),
+                Some((min, max)) => Some((
+                    Point::new(min.x().min(p.x()), min.y().min(p.y())),
+                    Point::new(max.x().max(p.x()), max.y().max(p.y())),
+                )),
+            })
+    }
+
+    /// Draws the cells from `min` to `max` with `#` for rock, `particle` for
+    /// what has come to rest and `.` for air.
+    pub(crate) fn render(&self, min: Point, max: Point, particle: char) -> String {
+        (min.y()..=max.y())
+            .map(|y| {
+                (min.x()..=max.x())
+                    .map(|x| match self.index(Point::new(x, y)) {
+                        Some(i) if get(&self.rock, i) => '#',
+                        Some(i) if get(&self.filled, i) => particle,
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
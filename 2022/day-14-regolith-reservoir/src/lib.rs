@@ -0,0 +1,521 @@
+use aoc_core::{
+    parse::{parse_all, parse_lines, tag, IResult, ParseError},
+    Part,
+};
+use aoc_grid::{Direction, Point, SparseGrid};
+use cave::Cave;
+use clap::{Args, ValueEnum};
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    character::complete::digit1,
+    combinator::{cut, map, map_res},
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
+};
+use std::{cmp, fmt, iter};
+
+mod animate;
+mod cave;
+
+pub use animate::{animate, ansi, frame_size, Cast, Every};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    Slanted { line: usize, from: Point, to: Point },
+    NoRock,
+    BelowFloor { source: Point, floor: i64 },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid rock path at {}", err),
+            Error::Slanted { line, from, to } => write!(
+                f,
+                "line {}: rock path from {},{} to {},{} is neither straight nor at 45 degrees",
+                line,
+                from.x(),
+                from.y(),
+                to.x(),
+                to.y()
+            ),
+            Error::NoRock => write!(f, "scan contains no rock paths"),
+            Error::BelowFloor { source, floor } => write!(
+                f,
+                "source {},{} is not above the floor at y={}",
+                source.x(),
+                source.y(),
+                floor
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+/// What is poured into the cave.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, ValueEnum)]
+pub enum Particle {
+    /// Falls straight down, else down to the left, else down to the right
+    #[default]
+    Sand,
+    /// Falls straight down, else flows left or right until it can fall or
+    /// is stopped
+    Water,
+}
+
+impl Particle {
+    fn symbol(self) -> char {
+        match self {
+            Particle::Sand => 'o',
+            Particle::Water => '~',
+        }
+    }
+
+    /// Where a particle moving `flow` tries to go next, in order, and which
+    /// way it is moving once there.
+    fn moves(self, flow: Flow) -> &'static [(Direction, Flow)] {
+        match (self, flow) {
+            (Particle::Sand, _) => &[
+                (Direction::S, Flow::Down),
+                (Direction::SW, Flow::Down),
+                (Direction::SE, Flow::Down),
+            ],
+            (Particle::Water, Flow::Down) => &[
+                (Direction::S, Flow::Down),
+                (Direction::W, Flow::Left),
+                (Direction::E, Flow::Right),
+            ],
+            (Particle::Water, Flow::Left) => {
+                &[(Direction::S, Flow::Down), (Direction::W, Flow::Left)]
+            }
+            (Particle::Water, Flow::Right) => {
+                &[(Direction::S, Flow::Down), (Direction::E, Flow::Right)]
+            }
+        }
+    }
+}
+
+impl fmt::Display for Particle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Particle::Sand => "sand",
+            Particle::Water => "water",
+        })
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Flow {
+    Down,
+    Left,
+    Right,
+}
+
+/// Where the cave is filled from, how deep its floor is and what it is
+/// filled with.
+#[derive(Clone, Eq, PartialEq, Debug, Args)]
+pub struct Rules {
+    /// Pour from X,Y as well as from the scan's own `source X,Y` lines;
+    /// with neither, the cave is filled from 500,0
+    #[arg(long = "source", value_name = "X,Y", value_parser = parse_source)]
+    pub sources: Vec<Point>,
+    /// How many rows below the lowest rock the floor lies
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(i64).range(1..))]
+    pub floor_offset: i64,
+    /// What is poured in
+    #[arg(long, value_enum, default_value_t = Particle::Sand)]
+    pub particle: Particle,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            sources: Vec::new(),
+            floor_offset: 2,
+            particle: Particle::Sand,
+        }
+    }
+}
+
+impl Rules {
+    fn sources(&self, scan: &Scan) -> Vec<Point> {
+        let sources: Vec<Point> = scan.sources.iter().chain(&self.sources).copied().collect();
+        if sources.is_empty() {
+            vec![Point::new(500, 0)]
+        } else {
+            sources
+        }
+    }
+}
+
+fn point(input: &str) -> IResult<'_, Point> {
+    map(
+        separated_pair(
+            map_res(digit1, str::parse::<i64>),
+            tag(","),
+            map_res(digit1, str::parse::<i64>),
+        ),
+        |(x, y)| Point::new(x, y),
+    )(input)
+}
+
+fn parse_source(s: &str) -> Result<Point, ParseError> {
+    parse_all(s, point)
+}
+
+enum Line {
+    Path(Vec<Point>),
+    Source(Point),
+}
+
+fn line(input: &str) -> IResult<'_, Line> {
+    alt((
+        map(preceded(tag("source "), cut(point)), Line::Source),
+        map(separated_list1(tag(" -> "), cut(point)), Line::Path),
+    ))(input)
+}
+
+/// The cells from `p1` to `p2`, if they lie in a straight line across, down
+/// or at 45 degrees. Lines at 45 degrees are drawn as staircases, as nothing
+/// would stop a particle moving diagonally from slipping through otherwise.
+fn draw(p1: &Point, p2: &Point) -> Option<impl Iterator<Item = Point>> {
+    let delta = *p2 - *p1;
+    let (dx, dy) = (delta.x().abs(), delta.y().abs());
+    let diagonal = dx != 0 && dy != 0;
+    if diagonal && dx != dy {
+        return None;
+    }
+    let (start, step, steps) = (*p1, delta.signum(), cmp::max(dx, dy));
+    Some((0..=steps).flat_map(move |i| {
+        let p = start + step * i;
+        let stair = (diagonal && i < steps).then(|| p + Point::new(step.x(), 0));
+        iter::once(p).chain(stair)
+    }))
+}
+
+/// The scanned rock, with the bounds sand can settle within before falling
+/// into the abyss, and any sources the scan names.
+pub struct Scan {
+    rock: SparseGrid<char>,
+    sources: Vec<Point>,
+    x_min: i64,
+    x_max: i64,
+    y_max: i64,
+}
+
+pub fn parse(input: &str) -> Result<Scan, Error> {
+    let mut rock: SparseGrid<char> = SparseGrid::new();
+    let mut sources = Vec::new();
+    for (i, line) in parse_lines(input, line)?.iter().enumerate() {
+        let path = match line {
+            Line::Path(path) => path,
+            Line::Source(source) => {
+                sources.push(*source);
+                continue;
+            }
+        };
+        for (from, to) in path.iter().tuple_windows() {
+            let line = draw(from, to).ok_or(Error::Slanted {
+                line: i + 1,
+                from: *from,
+                to: *to,
+            })?;
+            line.for_each(|point| {
+                rock.insert(point, '#');
+            });
+        }
+    }
+    let (min, max) = rock.bounds().ok_or(Error::NoRock)?;
+
+    Ok(Scan {
+        rock,
+        sources,
+        x_min: min.x(),
+        x_max: max.x(),
+        y_max: max.y(),
+    })
+}
+
+/// What a particle has just done, as the fill goes.
+pub(crate) enum Event {
+    Falling(Point),
+    Settled(usize),
+}
+
+/// The empty cave from `min` to `max` around the scanned rock.
+fn cave(scan: &Scan, (min, max): (Point, Point), floor: bool) -> Cave {
+    let mut cave = Cave::new(min, max, floor);
+    for p in scan.rock.points() {
+        cave.insert_rock(p);
+    }
+    cave
+}
+
+/// Moves a particle along `path` from its last cell until it comes to rest,
+/// returning where, or `None` if the path is empty or the particle leaves
+/// the cave. The path is left leading to the cell it rested in.
+fn drop(
+    cave: &mut Cave,
+    particle: Particle,
+    path: &mut Vec<(Point, Flow)>,
+    observe: &mut impl FnMut(&Cave, Event),
+) -> Option<Point> {
+    loop {
+        let &(p, flow) = path.last()?;
+        let next = particle
+            .moves(flow)
+            .iter()
+            .map(|&(d, flow)| (p + d, flow))
+            .find(|&(next, _)| !cave.is_filled(next));
+        match next {
+            // Nothing can settle outside the cave.
+            Some((next, _)) if !cave.contains(next) => return None,
+            Some(next) => {
+                path.push(next);
+                observe(cave, Event::Falling(next.0));
+            }
+            None => {
+                cave.insert_particle(p);
+                path.pop();
+                return Some(p);
+            }
+        }
+    }
+}
+
+fn simulate(
+    scan: &Scan,
+    rules: &Rules,
+    floor: bool,
+    mut observe: impl FnMut(&Cave, Event),
+) -> Result<(Cave, usize), Error> {
+    let mut cave = cave(scan, extent(scan, rules, floor)?, floor);
+    // Each particle from a source moves the way the last one from it did up
+    // to where that one came to rest, so the next starts from the cell
+    // before it.
+    let mut paths: Vec<Vec<(Point, Flow)>> = rules
+        .sources(scan)
+        .into_iter()
+        .map(|source| vec![(source, Flow::Down)])
+        .collect();
+    let mut settled = 0;
+    // The sources take turns until each is blocked or loses a particle.
+    while !paths.is_empty() {
+        paths.retain_mut(|path| {
+            // Particles from other sources may have come to rest on the path.
+            if let Some(i) = path.iter().position(|&(p, _)| cave.is_filled(p)) {
+                path.truncate(i);
+            }
+            if drop(&mut cave, rules.particle, path, &mut observe).is_none() {
+                return false;
+            }
+            settled += 1;
+            observe(&cave, Event::Settled(settled));
+            true
+        });
+    }
+
+    Ok((cave, settled))
+}
+
+fn fill(scan: &Scan, rules: &Rules, floor: bool) -> Result<(Cave, usize), Error> {
+    simulate(scan, rules, floor, |_, _| {})
+}
+
+/// The corners of the region particles can reach, and of the floor below
+/// it if there is one.
+fn extent(scan: &Scan, rules: &Rules, floor: bool) -> Result<(Point, Point), Error> {
+    let sources = rules.sources(scan);
+    let (mut x_min, mut x_max, mut y_max) = (scan.x_min, scan.x_max, scan.y_max);
+    for source in &sources {
+        x_min = cmp::min(x_min, source.x());
+        x_max = cmp::max(x_max, source.x());
+        y_max = cmp::max(y_max, source.y());
+    }
+    if floor {
+        y_max = scan.y_max + rules.floor_offset;
+        for &source in &sources {
+            if source.y() >= y_max {
+                return Err(Error::BelowFloor {
+                    source,
+                    floor: y_max,
+                });
+            }
+            if rules.particle == Particle::Sand {
+                // Sand piles up at most one column further out per row below
+                // its source.
+                let depth = y_max - 1 - source.y();
+                x_min = cmp::min(x_min, source.x() - depth);
+                x_max = cmp::max(x_max, source.x() + depth);
+            }
+        }
+    }
+    Ok((Point::new(x_min, 0), Point::new(x_max, y_max)))
+}
+
+/// Counts the sand that comes to rest above the floor without dropping it.
+/// The piles grow until they block every source, by which time each cell
+/// sand can reach is full: those that aren't rock and are a source or lie
+/// below a reachable cell or diagonally below one.
+pub fn floor_count(scan: &Scan, rules: &Rules) -> Result<usize, Error> {
+    let (min, max) = extent(scan, rules, true)?;
+    let width = (max.x() - min.x() + 1) as usize;
+    let cave = cave(scan, (min, max), true);
+    let sources = rules.sources(scan);
+    let mut row = vec![false; width];
+    let mut sand = 0;
+    for y in min.y()..max.y() {
+        row = (0..width)
+            .map(|x| {
+                let p = Point::new(min.x() + x as i64, y);
+                !cave.is_filled(p)
+                    && (sources.contains(&p)
+                        || row[x]
+                        || (x > 0 && row[x - 1])
+                        || (x + 1 < width && row[x + 1]))
+            })
+            .collect();
+        sand += row.iter().filter(|&&reached| reached).count();
+    }
+    Ok(sand)
+}
+
+/// How many particles come to rest under `rules`, with or without a floor.
+pub fn pour(scan: &Scan, rules: &Rules, floor: bool) -> Result<usize, Error> {
+    if floor && rules.particle == Particle::Sand {
+        floor_count(scan, rules)
+    } else {
+        Ok(fill(scan, rules, floor)?.1)
+    }
+}
+
+pub fn part1(scan: &Scan) -> Result<usize, Error> {
+    pour(scan, &Rules::default(), false)
+}
+
+pub fn part2(scan: &Scan) -> Result<usize, Error> {
+    pour(scan, &Rules::default(), true)
+}
+
+pub fn solve(input: &str) -> Result<(usize, usize), Error> {
+    let scan = parse(input)?;
+    Ok((part1(&scan)?, part2(&scan)?))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let scan = parse(input)?;
+    let result = match part {
+        Part::One => part1(&scan)?,
+        Part::Two => part2(&scan)?,
+    };
+
+    Ok(result.to_string())
+}
+
+pub fn visualise(input: &str, part: Part, rules: &Rules) -> Result<String, Error> {
+    let floor = part == Part::Two;
+    let scan = parse(input)?;
+    let (map, _) = fill(&scan, rules, floor)?;
+    let (min, max) = extent(&scan, rules, floor)?;
+    let (x_min, x_max) = if floor {
+        let (left, right) = map.bounds().unwrap();
+        (left.x(), right.x())
+    } else {
+        (min.x(), max.x())
+    };
+
+    Ok(map.render(
+        Point::new(x_min, min.y()),
+        Point::new(x_max, max.y()),
+        rules.particle.symbol(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fill, floor_count, parse, part1, part2, pour, Error, Particle, Rules};
+    use aoc_grid::Point;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn example() {
+        let scan = parse(EXAMPLE).unwrap();
+        assert_eq!((part1(&scan).unwrap(), part2(&scan).unwrap()), (24, 93));
+        assert_eq!(fill(&scan, &Rules::default(), true).unwrap().1, 93);
+    }
+
+    #[test]
+    fn counting_matches_filling() {
+        let two_sources = Rules {
+            sources: vec![Point::new(500, 0), Point::new(510, 3)],
+            ..Rules::default()
+        };
+        let deep = Rules {
+            floor_offset: 5,
+            ..Rules::default()
+        };
+        for (input, rules) in [
+            ("500,2 -> 500,2", Rules::default()),
+            ("490,3 -> 510,3", Rules::default()),
+            ("495,1 -> 505,1", deep.clone()),
+            (
+                "499,2 -> 499,5 -> 520,5\n480,6 -> 480,6",
+                two_sources.clone(),
+            ),
+            (EXAMPLE, two_sources),
+            (EXAMPLE, deep),
+        ] {
+            let scan = parse(input).unwrap();
+            let filled = fill(&scan, &rules, true).unwrap().1;
+            assert_eq!(filled, floor_count(&scan, &rules).unwrap(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn reads_sources_and_diagonal_walls() {
+        // A funnel with 45 degree sides, fed from off to one side.
+        let scan = parse("source 497,0\n494,2 -> 498,6 -> 502,2").unwrap();
+        assert_eq!(part1(&scan).unwrap(), 14);
+        let rules = Rules {
+            sources: vec![Point::new(501, 0)],
+            ..Rules::default()
+        };
+        assert_eq!(pour(&scan, &rules, false).unwrap(), 18);
+        assert!(matches!(
+            parse("494,2 -> 497,3"),
+            Err(Error::Slanted { line: 1, .. })
+        ));
+        let low = Rules {
+            sources: vec![Point::new(500, 8)],
+            ..Rules::default()
+        };
+        assert!(matches!(
+            pour(&scan, &low, true),
+            Err(Error::BelowFloor { floor: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn water_spreads_sideways() {
+        let scan = parse("495,2 -> 495,5 -> 505,5 -> 505,2").unwrap();
+        let water = Rules {
+            particle: Particle::Water,
+            ..Rules::default()
+        };
+        assert_eq!(pour(&scan, &water, false).unwrap(), 27);
+        // With a floor the overflow runs off along it past the walls.
+        assert_eq!(pour(&scan, &water, true).unwrap(), 27);
+        // Sand heaps up in the middle instead, and the heap blocks the source.
+        assert_eq!(pour(&scan, &Rules::default(), false).unwrap(), 25);
+    }
+}
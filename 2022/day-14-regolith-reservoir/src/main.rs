@@ -1,163 +1,119 @@
+use aoc_core::{Input, Output, Part};
 use clap::Parser;
-use itertools::{Either, Itertools};
-use nom::{
-    bytes::complete::tag,
-    character::complete::digit1,
-    combinator::{map, map_res},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
+use day_14_regolith_reservoir::{
+    animate, ansi, frame_size, parse, pour, solve, solve_part, visualise, Cast, Every, Rules,
+};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    thread,
+    time::Duration,
 };
-use std::{cmp, collections::BTreeMap, io};
 
 #[derive(Parser)]
+// `Rules` has no floor of its own to depend on, so the offset is tied to
+// this binary's --floor here.
+#[command(mut_arg("floor_offset", |arg| arg.requires("floor")))]
 struct Args {
     #[arg(long, default_value_t = false)]
     floor: bool,
     #[arg(long, default_value_t = false)]
     vis: bool,
-}
-
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-
-enum Dimension {
-    X,
-    Y,
-}
-
-fn path(input: &str) -> IResult<&str, Vec<Point>> {
-    separated_list1(
-        tag(" -> "),
-        map(
-            separated_pair(
-                map_res(digit1, str::parse::<usize>),
-                tag(","),
-                map_res(digit1, str::parse::<usize>),
-            ),
-            |(x, y)| Point { x, y },
-        ),
-    )(input)
-}
-
-fn draw((p1, p2): (&Point, &Point)) -> impl Iterator<Item = Point> {
-    let &Point { x, y } = p1;
-    let x_min = cmp::min(p1.x, p2.x);
-    let x_max = cmp::max(p1.x, p2.x);
-    let y_min = cmp::min(p1.y, p2.y);
-    let y_max = cmp::max(p1.y, p2.y);
-    match (x_max - x_min, y_max - y_min) {
-        (_, 0) => Either::Left((x_min..=x_max).map(move |x| Point { x, y })),
-        (0, _) => Either::Right((y_min..=y_max).map(move |y| Point { x, y })),
-        (_, _) => panic!("diagonal draw"),
-    }
-}
-
-fn drop(
-    x: usize,
-    map: &BTreeMap<Point, char>,
-    can_move: impl Fn(Point, &BTreeMap<Point, char>, bool) -> bool,
-) -> Option<Point> {
-    let mut p = Point { x, y: 0 };
-    loop {
-        let down = Point { x: p.x, y: p.y + 1 };
-        let down_left = Point {
-            x: p.x - 1,
-            y: p.y + 1,
-        };
-        let down_right = Point {
-            x: p.x + 1,
-            y: p.y + 1,
-        };
-        if can_move(down, map, false) {
-            p = down;
-        } else if can_move(down_left, map, false) {
-            p = down_left;
-        } else if can_move(down_right, map, false) {
-            p = down_right;
-        } else {
-            break;
-        }
-        if !can_move(p, map, true) {
-            break;
-        }
-    }
-    if can_move(p, map, true) {
-        Some(p)
-    } else {
-        None
-    }
-}
-
-fn bounds<'a, I>(i: I, d: Dimension) -> (usize, usize)
-where
-    I: Iterator<Item = &'a Point>,
-{
-    i.map(|&Point { x, y }| match d {
-        Dimension::X => x,
-        Dimension::Y => y,
-    })
-    .minmax()
-    .into_option()
-    .unwrap()
+    /// Redraw the cave in the terminal as the sand falls
+    #[arg(long, default_value_t = false)]
+    animate: bool,
+    /// Frames per second to animate or record at
+    #[arg(long, default_value_t = 30.0)]
+    fps: f64,
+    /// Draw a frame each time this many particles have settled
+    #[arg(long, value_name = "N", default_value_t = 1,
+          value_parser = clap::value_parser!(u64).range(1..))]
+    every: u64,
+    /// Draw a frame at each step of each falling particle instead
+    #[arg(long, default_value_t = false, conflicts_with = "every")]
+    steps: bool,
+    /// Record the animation as an asciicast v2 file
+    #[arg(long, value_name = "PATH")]
+    cast: Option<PathBuf>,
+    #[command(flatten)]
+    rules: Rules,
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let Args { floor, vis } = Args::parse();
-    let input: Vec<Vec<Point>> = io::stdin()
-        .lines()
-        .map(|line| path(&line.unwrap()).map(|(_, path)| path).unwrap())
-        .collect();
-    let mut map: BTreeMap<Point, char> = BTreeMap::new();
-    input.iter().for_each(|path| {
-        path.iter().tuple_windows().map(draw).for_each(|line| {
-            line.for_each(|point| {
-                map.insert(point, '#');
-            });
-        });
-    });
-    let (x_min, x_max) = bounds(input.iter().flatten(), Dimension::X);
-    let (_, y_max) = bounds(input.iter().flatten(), Dimension::Y);
-    let can_move = |p: Point, map: &BTreeMap<Point, char>, check_bounds: bool| {
-        if floor {
-            p.y < y_max + 2 && map.get(&p).is_none()
-        } else if check_bounds {
-            p.x >= x_min && p.x <= x_max && p.y <= y_max
-        } else {
-            map.get(&p).is_none()
-        }
-    };
-    let mut result = 0;
-    while let Some(point) = drop(500, &map, can_move) {
-        map.insert(point, 'o');
-        result += 1;
+    let Args {
+        floor,
+        vis,
+        animate: animated,
+        fps,
+        every,
+        steps,
+        cast,
+        rules,
+        input,
+        output,
+    } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(env!("CARGO_MANIFEST_DIR"), &input, &Part::ALL, solve_part) {
+        return;
     }
+    let part = if floor { Part::Two } else { Part::One };
     if vis {
-        let (x_min, x_max) = if floor {
-            bounds(map.keys(), Dimension::X)
+        let map = visualise(&input, part, &rules).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", map);
+    }
+    if animated || cast.is_some() {
+        if !(fps.is_finite() && fps > 0.0) {
+            aoc_core::fail(format!("--fps must be positive, not {}", fps));
+        }
+        let scan = parse(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        let every = if steps {
+            Every::Step
         } else {
-            (x_min, x_max)
+            Every::Grains(every as usize)
+        };
+        let fail = |path: &PathBuf, err: io::Error| -> ! {
+            aoc_core::fail(format!("cannot write {}: {}", path.display(), err))
         };
-        let y_max = if floor { y_max + 1 } else { y_max };
-        for y in 0..=y_max {
-            for x in x_min..=x_max {
-                if let Some(c) = map.get(&Point { x, y }) {
-                    print!("{}", c);
-                } else {
-                    print!(".");
-                }
+        let mut recording = cast.as_ref().map(|path| {
+            let file = File::create(path).unwrap_or_else(|err| fail(path, err));
+            let size = frame_size(&scan, &rules, floor).unwrap_or_else(|err| aoc_core::fail(err));
+            Cast::new(BufWriter::new(file), size, fps).unwrap_or_else(|err| fail(path, err))
+        });
+        let delay = Duration::from_secs_f64(1.0 / fps);
+        let mut first = true;
+        animate(&scan, &rules, floor, every, |frame| {
+            if animated {
+                let mut stdout = io::stdout().lock();
+                // A closed terminal just ends the show early.
+                let _ = write!(stdout, "{}", ansi(frame, first)).and_then(|_| stdout.flush());
+                thread::sleep(delay);
             }
-            println!();
-        }
-        if floor {
-            for _ in x_min..=x_max {
-                print!("#");
+            if let (Some(recording), Some(path)) = (recording.as_mut(), cast.as_ref()) {
+                recording.frame(frame).unwrap_or_else(|err| fail(path, err));
             }
-            println!();
+            first = false;
+        })
+        .unwrap_or_else(|err| aoc_core::fail(err));
+        if let (Some(recording), Some(path)) = (recording, cast.as_ref()) {
+            recording.finish().unwrap_or_else(|err| fail(path, err));
         }
     }
-    println!("{}", result);
+    if rules != Rules::default() {
+        let scan = parse(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        let answer = pour(&scan, &rules, floor).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", answer);
+    } else if floor {
+        let answer = solve_part(&input, Part::Two).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", answer);
+    } else {
+        let (part1, part2) = solve(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        aoc_core::print_answers(part1, part2);
+    }
 }
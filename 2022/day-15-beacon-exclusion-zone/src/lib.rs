@@ -0,0 +1,185 @@
+use aoc_core::{
+    parse::{parse_lines, tag, IResult, ParseError},
+    Part,
+};
+use aoc_point::Point2;
+use nom::{
+    character::complete::digit1,
+    combinator::{map, map_res, opt, recognize},
+    sequence::{preceded, tuple},
+};
+use std::{
+    cmp,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    NoGap { depth: i32 },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid sensor report at {}", err),
+            Error::NoGap { depth } => {
+                write!(
+                    f,
+                    "no undetected position with x and y in 0..={}",
+                    depth * 2
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+pub const DEPTH: i32 = 2000000;
+/// The row the puzzle scans in its example.
+pub const EXAMPLE_DEPTH: i32 = 10;
+
+type Point = Point2<i32>;
+
+fn find_nearest<'a>(p: &Point, i: impl Iterator<Item = &'a Point>) -> Option<&'a Point> {
+    i.min_by_key(|q| p.manhattan(q))
+}
+
+fn signed<T: FromStr>(input: &str) -> IResult<'_, T> {
+    map_res(recognize(preceded(opt(tag("-")), digit1)), str::parse::<T>)(input)
+}
+
+fn data(input: &str) -> IResult<'_, (Point, Point)> {
+    tuple((
+        map(
+            tuple((
+                preceded(tag("Sensor at x="), signed::<i32>),
+                preceded(tag(", y="), signed::<i32>),
+            )),
+            |(x, y)| Point::new(x, y),
+        ),
+        map(
+            tuple((
+                preceded(tag(": closest beacon is at x="), signed::<i32>),
+                preceded(tag(", y="), signed::<i32>),
+            )),
+            |(x, y)| Point::new(x, y),
+        ),
+    ))(input)
+}
+
+fn x_range(p: Point, distance: i32, y_intercept: i32) -> Option<RangeInclusive<i32>> {
+    let (x, y) = (p.x(), p.y());
+    let dy = (y - y_intercept).abs();
+    if dy < distance {
+        let dx = distance - dy;
+        Some(x - dx..=x + dx)
+    } else {
+        None
+    }
+}
+
+fn merge<T>(ranges: &[RangeInclusive<T>]) -> Vec<RangeInclusive<T>>
+where
+    T: Ord + Clone,
+{
+    let mut ranges = ranges.to_vec();
+    ranges.sort_unstable_by_key(|range| range.start().clone());
+    ranges.iter().fold(Vec::new(), move |mut merged, range| {
+        let last = merged.last();
+        if last.is_none() || last.unwrap().end() < range.start() {
+            merged.push(range.clone());
+        } else {
+            let last = merged.pop().unwrap();
+            merged.push(last.start().clone()..=cmp::max(last.end().clone(), range.end().clone()))
+        }
+
+        merged
+    })
+}
+
+fn sum(ranges: &[RangeInclusive<i32>]) -> i32 {
+    ranges
+        .iter()
+        .fold(0, |sum, range| sum + range.end() - range.start())
+}
+
+/// Each sensor with the distance to its nearest beacon.
+pub type Sensors = BTreeMap<Point, i32>;
+
+pub fn parse(input: &str) -> Result<Sensors, Error> {
+    let input: Vec<(Point, Point)> = parse_lines(input, data)?;
+    let beacons: BTreeSet<Point> = input.iter().map(|&(_, beacon)| beacon).collect();
+
+    Ok(input
+        .iter()
+        .flat_map(|&(sensor, _)| {
+            find_nearest(&sensor, beacons.iter()).map(|beacon| (sensor, sensor.manhattan(beacon)))
+        })
+        .collect())
+}
+
+pub fn excluded(sensors: &Sensors, depth: i32) -> u64 {
+    sum(&merge(
+        &sensors
+            .iter()
+            .flat_map(|(&point, &distance)| x_range(point, distance, depth))
+            .collect::<Vec<RangeInclusive<i32>>>(),
+    )) as u64
+}
+
+pub fn tuning_frequency(sensors: &Sensors, depth: i32) -> Result<u64, Error> {
+    (0..=depth * 2)
+        .find_map(|y| {
+            let ranges = merge(
+                &sensors
+                    .iter()
+                    .flat_map(|(&point, &distance)| x_range(point, distance, y))
+                    .collect::<Vec<RangeInclusive<i32>>>(),
+            );
+            if ranges.len() > 1 {
+                Some((ranges[0].end() + 1) as u64 * 4000000u64 + y as u64)
+            } else {
+                None
+            }
+        })
+        .ok_or(Error::NoGap { depth })
+}
+
+pub fn part1(sensors: &Sensors) -> u64 {
+    excluded(sensors, DEPTH)
+}
+
+pub fn part2(sensors: &Sensors) -> Result<u64, Error> {
+    tuning_frequency(sensors, DEPTH)
+}
+
+pub fn solve(input: &str) -> Result<(u64, u64), Error> {
+    let sensors = parse(input)?;
+    Ok((part1(&sensors), part2(&sensors)?))
+}
+
+/// Solves one part with the row to scan set to `depth` rather than [`DEPTH`].
+pub fn solve_part_at(input: &str, part: Part, depth: i32) -> Result<String, Error> {
+    let sensors = parse(input)?;
+    let result = match part {
+        Part::One => excluded(&sensors, depth),
+        Part::Two => tuning_frequency(&sensors, depth)?,
+    };
+
+    Ok(result.to_string())
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    solve_part_at(input, part, DEPTH)
+}
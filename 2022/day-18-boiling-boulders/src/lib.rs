@@ -0,0 +1,119 @@
+use aoc_core::{
+    parse::{parse_lines, tag, IResult, ParseError},
+    Part,
+};
+use aoc_point::Point3;
+use itertools::Itertools;
+use nom::{
+    character::complete::digit1,
+    combinator::{map, map_res},
+    sequence::{preceded, tuple},
+};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt,
+    str::FromStr,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    NoCubes,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid cube at {}", err),
+            Error::NoCubes => write!(f, "scan contains no cubes"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+pub type Point = Point3<i32>;
+
+fn cube<T>(input: &str) -> IResult<'_, Point3<T>>
+where
+    T: Copy + FromStr,
+{
+    map(
+        tuple((
+            map_res(digit1, str::parse::<T>),
+            preceded(tag(","), map_res(digit1, str::parse::<T>)),
+            preceded(tag(","), map_res(digit1, str::parse::<T>)),
+        )),
+        |(x, y, z)| Point3::new(x, y, z),
+    )(input)
+}
+
+fn bounds<'a, I>(i: I, axis: usize) -> Result<(i32, i32), Error>
+where
+    I: Iterator<Item = &'a Point>,
+{
+    i.map(|p| p[axis])
+        .minmax()
+        .into_option()
+        .ok_or(Error::NoCubes)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Point>, Error> {
+    Ok(parse_lines(input, cube)?)
+}
+
+pub fn part1(input: &[Point]) -> usize {
+    let cubes: HashSet<Point> = input.iter().copied().collect();
+    input
+        .iter()
+        .flat_map(|p| p.von_neumann())
+        .filter(|q| !cubes.contains(q))
+        .count()
+}
+
+pub fn part2(input: &[Point]) -> Result<usize, Error> {
+    let cubes: HashSet<Point> = input.iter().copied().collect();
+    let (x_min, x_max) = bounds(input.iter(), 0)?;
+    let (y_min, y_max) = bounds(input.iter(), 1)?;
+    let (z_min, z_max) = bounds(input.iter(), 2)?;
+    let min = Point::new(x_min - 1, y_min - 1, z_min - 1);
+    let max = Point::new(x_max + 1, y_max + 1, z_max + 1);
+    let in_bounds = |p: &Point| (0..3).all(|i| (min[i]..=max[i]).contains(&p[i]));
+    let mut flood: VecDeque<Point> = VecDeque::new();
+    let mut void: HashSet<Point> = HashSet::new();
+    let mut exterior = 0;
+    flood.push_back(min);
+    void.insert(min);
+    while let Some(p) = flood.pop_front() {
+        for q in p.von_neumann().filter(in_bounds) {
+            if cubes.contains(&q) {
+                exterior += 1;
+            } else if void.insert(q) {
+                flood.push_back(q);
+            }
+        }
+    }
+
+    Ok(exterior)
+}
+
+pub fn solve(input: &str) -> Result<(usize, usize), Error> {
+    let cubes = parse(input)?;
+    Ok((part1(&cubes), part2(&cubes)?))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let input = parse(input)?;
+    let result = match part {
+        Part::One => part1(&input),
+        Part::Two => part2(&input)?,
+    };
+
+    Ok(result.to_string())
+}
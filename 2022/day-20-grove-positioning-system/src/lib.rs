@@ -0,0 +1,87 @@
+use aoc_core::{
+    parse::{parse_lines, ParseError},
+    Part,
+};
+use nom::character::complete::i64;
+use std::{collections::VecDeque, fmt};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    NoZero,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid number at {}", err),
+            Error::NoZero => write!(f, "file contains no 0"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, Error> {
+    Ok(parse_lines(input, i64)?)
+}
+
+fn grove_coordinates(numbers: &[i64], decrypt: bool) -> Result<i64, Error> {
+    let mut input: VecDeque<(i64, usize)> = numbers
+        .iter()
+        .enumerate()
+        .map(|(i, &n)| (n * if decrypt { 811589153 } else { 1 }, i))
+        .collect();
+    (0..if decrypt { 10 } else { 1 }).for_each(|_| {
+        (0..input.len()).for_each(|i| {
+            let j = input.iter().position(|&(_, o)| o == i).unwrap();
+            if let Some((n, _)) = input.remove(j) {
+                let len = input.len() as i64;
+                let j = ((((len + j as i64 + n) % len) + len) % len) as usize;
+                input.insert(j, (n, i));
+            }
+        });
+    });
+    let offset = input
+        .iter()
+        .position(|&(n, _)| n == 0)
+        .ok_or(Error::NoZero)?;
+
+    Ok((1..=3)
+        .map(|i| {
+            let i = (i as usize * 1000 + offset) % input.len();
+            let (n, _) = input.get(i).unwrap();
+
+            n
+        })
+        .sum())
+}
+
+pub fn part1(numbers: &[i64]) -> Result<i64, Error> {
+    grove_coordinates(numbers, false)
+}
+
+pub fn part2(numbers: &[i64]) -> Result<i64, Error> {
+    grove_coordinates(numbers, true)
+}
+
+pub fn solve(input: &str) -> Result<(i64, i64), Error> {
+    let numbers = parse(input)?;
+    Ok((part1(&numbers)?, part2(&numbers)?))
+}
+
+pub fn solve_part(input: &str, part: Part) -> Result<String, Error> {
+    let numbers = parse(input)?;
+    let result = match part {
+        Part::One => part1(&numbers)?,
+        Part::Two => part2(&numbers)?,
+    };
+
+    Ok(result.to_string())
+}
@@ -1,42 +1,34 @@
+use aoc_core::{Input, Output, Part};
 use clap::Parser;
-use std::{collections::VecDeque, io};
+use day_20_grove_positioning_system::{solve, solve_part};
 
 #[derive(Parser)]
 struct Args {
     #[arg(long, default_value_t = false)]
     decrypt: bool,
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let Args { decrypt } = Args::parse();
-    let mut input: VecDeque<(i64, usize)> = io::stdin()
-        .lines()
-        .enumerate()
-        .map(|(i, n)| {
-            (
-                n.unwrap().parse::<i64>().unwrap() * if decrypt { 811589153 } else { 1 },
-                i,
-            )
-        })
-        .collect();
-    (0..if decrypt { 10 } else { 1 }).for_each(|_| {
-        (0..input.len()).for_each(|i| {
-            let j = input.iter().position(|&(_, o)| o == i).unwrap();
-            if let Some((n, _)) = input.remove(j) {
-                let len = input.len() as i64;
-                let j = ((((len + j as i64 + n) % len) + len) % len) as usize;
-                input.insert(j, (n, i));
-            }
-        });
-    });
-    let offset = input.iter().position(|&(n, _)| n == 0).unwrap();
-    let result: i64 = (1..=3)
-        .map(|i| {
-            let i = (i as usize * 1000 + offset) % input.len();
-            let (n, _) = input.get(i).unwrap();
-
-            n
-        })
-        .sum();
-    println!("{}", result);
+    let Args {
+        decrypt,
+        input,
+        output,
+    } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(env!("CARGO_MANIFEST_DIR"), &input, &Part::ALL, solve_part) {
+        return;
+    }
+    if decrypt {
+        let answer = solve_part(&input, Part::Two).unwrap_or_else(|err| aoc_core::fail(err));
+        println!("{}", answer);
+    } else {
+        let (part1, part2) = solve(&input).unwrap_or_else(|err| aoc_core::fail(err));
+        aoc_core::print_answers(part1, part2);
+    }
 }
@@ -0,0 +1,174 @@
+use aoc_core::Part;
+use aoc_grid::{Direction, Point, SparseGrid};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug)]
+struct Elf {
+    current_position: Point,
+    proposed_move: Option<Point>,
+}
+
+impl Elf {
+    fn new(current_position: Point) -> Self {
+        Elf {
+            current_position,
+            proposed_move: None,
+        }
+    }
+}
+
+fn neighbors(s: &SparseGrid<char>, &p: &Point) -> HashSet<Direction> {
+    Direction::ALL
+        .into_iter()
+        .filter(|&d| s.contains(p + d))
+        .collect()
+}
+
+fn can_move(neighbors: &HashSet<Direction>, d: &Direction) -> bool {
+    match d {
+        Direction::N => {
+            !neighbors.contains(&Direction::N)
+                && !neighbors.contains(&Direction::NE)
+                && !neighbors.contains(&Direction::NW)
+        }
+        Direction::E => {
+            !neighbors.contains(&Direction::E)
+                && !neighbors.contains(&Direction::NE)
+                && !neighbors.contains(&Direction::SE)
+        }
+        Direction::S => {
+            !neighbors.contains(&Direction::S)
+                && !neighbors.contains(&Direction::SE)
+                && !neighbors.contains(&Direction::SW)
+        }
+        Direction::W => {
+            !neighbors.contains(&Direction::W)
+                && !neighbors.contains(&Direction::NW)
+                && !neighbors.contains(&Direction::SW)
+        }
+        _ => panic!("invalid move"),
+    }
+}
+
+fn positions(elves: &[Elf]) -> SparseGrid<char> {
+    elves
+        .iter()
+        .map(|elf| (elf.current_position, '#'))
+        .collect()
+}
+
+pub fn parse(input: &str) -> Vec<Point> {
+    input
+        .parse::<SparseGrid<char>>()
+        .unwrap()
+        .iter()
+        .filter(|&(_, &c)| c == '#')
+        .map(|(p, _)| p)
+        .collect()
+}
+
+fn spread(start: &[Point], complete: bool) -> (String, usize) {
+    let mut elves: Vec<Elf> = start.iter().map(|&p| Elf::new(p)).collect();
+    let mut round = 0;
+
+    for (a, b, c, d) in [Direction::N, Direction::S, Direction::W, Direction::E]
+        .iter()
+        .cycle()
+        .tuple_windows()
+    {
+        let current_positions = positions(&elves);
+
+        round += 1;
+        elves.iter_mut().for_each(
+            |Elf {
+                 current_position,
+                 proposed_move,
+             }| {
+                let neighbors = neighbors(&current_positions, current_position);
+                if neighbors.is_empty() {
+                    *proposed_move = None;
+                } else if can_move(&neighbors, a) {
+                    *proposed_move = Some(*current_position + *a);
+                } else if can_move(&neighbors, b) {
+                    *proposed_move = Some(*current_position + *b);
+                } else if can_move(&neighbors, c) {
+                    *proposed_move = Some(*current_position + *c);
+                } else if can_move(&neighbors, d) {
+                    *proposed_move = Some(*current_position + *d);
+                }
+            },
+        );
+
+        let proposed_moves = elves.iter().fold(
+            HashMap::new(),
+            |mut proposed_moves, Elf { proposed_move, .. }| {
+                if let Some(proposed_move) = proposed_move {
+                    proposed_moves
+                        .entry(*proposed_move)
+                        .and_modify(|n| *n += 1)
+                        .or_insert(1);
+                }
+
+                proposed_moves
+            },
+        );
+
+        if proposed_moves.is_empty() {
+            break;
+        }
+
+        elves.iter_mut().for_each(
+            |Elf {
+                 current_position,
+                 proposed_move,
+             }| {
+                if let Some(proposed_move) = proposed_move {
+                    if *proposed_moves.get(proposed_move).unwrap() == 1 {
+                        *current_position = *proposed_move;
+                    }
+                }
+            },
+        );
+
+        if !complete && round == 10 {
+            break;
+        }
+    }
+
+    let map = positions(&elves).to_string();
+    let result = if complete {
+        round
+    } else {
+        map.matches('.').count()
+    };
+
+    (map, result)
+}
+
+pub fn part1(elves: &[Point]) -> usize {
+    spread(elves, false).1
+}
+
+pub fn part2(elves: &[Point]) -> usize {
+    spread(elves, true).1
+}
+
+pub fn solve(input: &str) -> (usize, usize) {
+    let elves = parse(input);
+    (part1(&elves), part2(&elves))
+}
+
+pub fn solve_part(input: &str, part: Part) -> String {
+    let elves = parse(input);
+    match part {
+        Part::One => part1(&elves),
+        Part::Two => part2(&elves),
+    }
+    .to_string()
+}
+
+pub fn visualise(input: &str, part: Part) -> String {
+    let (map, _) = spread(&parse(input), part == Part::Two);
+    map
+}
@@ -0,0 +1,63 @@
+use aoc_core::{
+    parse::{parse_lines, ParseError},
+    Part,
+};
+use nom::{
+    character::complete::{space1, u32},
+    sequence::separated_pair,
+};
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid location ids at {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+/// Both location lists, each sorted.
+pub type Lists = (Vec<u32>, Vec<u32>);
+
+pub fn parse(input: &str) -> Result<Lists, Error> {
+    let (mut l, mut r): Lists = parse_lines(input, separated_pair(u32, space1, u32))?
+        .into_iter()
+        .unzip();
+    l.sort();
+    r.sort();
+
+    Ok((l, r))
+}
+
+pub fn part1((l, r): &Lists) -> u32 {
+    l.iter()
+        .enumerate()
+        .fold(0, |d, (i, &x)| d + r[i].abs_diff(x))
+}
+
+pub fn part2((l, r): &Lists) -> u32 {
+    l.iter().fold(0, |s, &x| {
+        s + x * r.iter().filter(|&n| *n == x).count() as u32
+    })
+}
+
+pub fn solve(input: &str, part: Part) -> Result<String, Error> {
+    let lists = parse(input)?;
+    Ok(match part {
+        Part::One => part1(&lists).to_string(),
+        Part::Two => part2(&lists).to_string(),
+    })
+}
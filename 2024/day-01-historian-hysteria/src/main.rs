@@ -1,21 +1,24 @@
-use std::io;
+use aoc_core::{Input, Output, Part};
+use clap::Parser;
+use day_01_historian_hysteria::{parse, part1, part2, solve};
+
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
+}
 
 fn main() {
-    let (mut l, mut r) = io::stdin()
-        .lines()
-        .fold((vec![], vec![]), |(mut l, mut r), line| {
-            if let Some((x, y)) = line.unwrap().split_once("   ") {
-                l.push(x.parse::<u32>().unwrap());
-                r.push(y.parse::<u32>().unwrap());
-            }
-            (l, r)
-        });
-    l.sort();
-    r.sort();
-    let distance = l.iter().enumerate().fold(0, |d, (i, &x)| d + r[i].abs_diff(x));
-    let similarity = l.iter().fold(0, |s, &x| {
-        s + x * r.iter().filter(|&n| *n == x).count() as u32
-    });
+    let Args { input, output } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(env!("CARGO_MANIFEST_DIR"), &input, &Part::ALL, solve) {
+        return;
+    }
+    let lists = parse(&input).unwrap_or_else(|err| aoc_core::fail(err));
 
-    println!("distance: {:?}, similarity: {:?}", distance, similarity);
+    println!("distance: {}, similarity: {}", part1(&lists), part2(&lists));
 }
@@ -0,0 +1,82 @@
+use aoc_core::{
+    parse::{parse_lines, tag, ParseError},
+    Part,
+};
+use nom::{character::complete::u32, multi::separated_list1};
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid report at {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+fn is_monotonic(vals: &[u32], max_diff: u32) -> bool {
+    let (is_monotonic, _) =
+        vals.windows(2)
+            .fold((true, None), |(mut is_monotonic, mut ascending), pair| {
+                if let [prev, next] = pair {
+                    let greater = next > prev;
+                    let ok_diff = next != prev && next.abs_diff(*prev) <= max_diff;
+                    match ascending {
+                        Some(asc) => {
+                            is_monotonic = is_monotonic && asc == greater && ok_diff;
+                        }
+                        None => {
+                            is_monotonic = ok_diff;
+                            ascending = Some(greater);
+                        }
+                    }
+                }
+
+                (is_monotonic, ascending)
+            });
+
+    is_monotonic
+}
+
+fn can_dampen(vals: &[u32]) -> bool {
+    (0..vals.len()).any(|i| {
+        let mut dampened = vals.to_vec();
+        dampened.remove(i);
+        is_monotonic(&dampened, 3)
+    })
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<u32>>, Error> {
+    Ok(parse_lines(input, separated_list1(tag(" "), u32))?)
+}
+
+pub fn part1(reports: &[Vec<u32>]) -> usize {
+    reports.iter().filter(|vals| is_monotonic(vals, 3)).count()
+}
+
+pub fn part2(reports: &[Vec<u32>]) -> usize {
+    reports
+        .iter()
+        .filter(|vals| is_monotonic(vals, 3) || can_dampen(vals))
+        .count()
+}
+
+pub fn solve(input: &str, part: Part) -> Result<String, Error> {
+    let reports = parse(input)?;
+    Ok(match part {
+        Part::One => part1(&reports).to_string(),
+        Part::Two => part2(&reports).to_string(),
+    })
+}
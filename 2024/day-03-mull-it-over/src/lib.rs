@@ -0,0 +1,80 @@
+use aoc_core::Part;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take},
+    character::complete::{char, u32},
+    combinator::map,
+    multi::{many0, many_till},
+    sequence::{delimited, separated_pair},
+    IResult,
+};
+
+pub enum Instuction {
+    Do,
+    Dont,
+    Mul(u32, u32),
+}
+
+fn get_instructionsx(input: &str) -> IResult<&str, Vec<Instuction>> {
+    many0(map(
+        many_till(
+            take(1usize),
+            alt((
+                map(tag("do()"), |_| Instuction::Do),
+                map(tag("don't()"), |_| Instuction::Dont),
+                map(
+                    delimited(tag("mul("), separated_pair(u32, char(','), u32), char(')')),
+                    |(l, r)| Instuction::Mul(l, r),
+                ),
+            )),
+        ),
+        |(_, instr)| instr,
+    ))(input)
+}
+
+pub fn parse(input: &str) -> Vec<Instuction> {
+    input
+        .lines()
+        .flat_map(|line| get_instructionsx(line).map(|(_, instr)| instr).unwrap())
+        .collect()
+}
+
+pub fn part1(args: &[Instuction]) -> u32 {
+    args.iter()
+        .map(|instr| match instr {
+            Instuction::Do => 0,
+            Instuction::Dont => 0,
+            Instuction::Mul(l, r) => l * r,
+        })
+        .sum()
+}
+
+pub fn part2(args: &[Instuction]) -> u32 {
+    let (conditional, _) = args
+        .iter()
+        .fold((0, true), |(mut sum, mut enabled), instr| {
+            match instr {
+                Instuction::Do => {
+                    enabled = true;
+                }
+                Instuction::Dont => {
+                    enabled = false;
+                }
+                Instuction::Mul(l, r) => {
+                    if enabled {
+                        sum += l * r;
+                    }
+                }
+            };
+            (sum, enabled)
+        });
+    conditional
+}
+
+pub fn solve(input: &str, part: Part) -> String {
+    let args = parse(input);
+    match part {
+        Part::One => part1(&args).to_string(),
+        Part::Two => part2(&args).to_string(),
+    }
+}
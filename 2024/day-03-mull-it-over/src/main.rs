@@ -1,82 +1,32 @@
-use std::io;
-
-use nom::{
-    branch::alt,
-    bytes::complete::{tag, take},
-    character::complete::{char, digit1},
-    combinator::map,
-    multi::{many0, many_till},
-    sequence::{delimited, separated_pair},
-    IResult,
-};
-
-enum Instuction {
-    Do,
-    Dont,
-    Mul(u32, u32),
-}
-
-fn get_instructionsx(input: &str) -> IResult<&str, Vec<Instuction>> {
-    many0(map(
-        many_till(
-            take(1usize),
-            alt((
-                map(tag("do()"), |_| Instuction::Do),
-                map(tag("don't()"), |_| Instuction::Dont),
-                map(
-                    delimited(
-                        tag("mul("),
-                        separated_pair(digit1::<&str, _>, char(','), digit1),
-                        char(')'),
-                    ),
-                    |(l, r)| Instuction::Mul(l.parse().unwrap(), r.parse().unwrap()),
-                ),
-            )),
-        ),
-        |(_, instr)| instr,
-    ))(input)
+use aoc_core::{Input, Output, Part};
+use clap::Parser;
+use day_03_mull_it_over::{parse, part1, part2, solve};
+use std::convert::Infallible;
+
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    input: Input,
+    #[command(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let args: Vec<Instuction> = io::stdin()
-        .lines()
-        .map(|line| {
-            get_instructionsx(&line.unwrap())
-                .map(|(_, instr)| instr)
-                .unwrap()
-        })
-        .flatten()
-        .collect();
-
-    let result: u32 = args
-        .iter()
-        .map(|instr| match instr {
-            Instuction::Do => 0,
-            Instuction::Dont => 0,
-            Instuction::Mul(l, r) => l * r,
-        })
-        .sum();
-
-    let (conditional, _) = args
-        .iter()
-        .fold((0, true), |(mut sum, mut enabled), instr| {
-            match instr {
-                Instuction::Do => {
-                    enabled = true;
-                }
-                Instuction::Dont => {
-                    enabled = false;
-                }
-                Instuction::Mul(l, r) => {
-                    if enabled {
-                        sum += l * r;
-                    }
-                }
-            };
-
-            (sum, enabled)
-        });
-
-    println!("result: {}", result);
-    println!("conditional: {}", conditional);
+    let Args { input, output } = Args::parse();
+    let input = input
+        .read(env!("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|err| aoc_core::fail(err));
+    if output.report(
+        env!("CARGO_MANIFEST_DIR"),
+        &input,
+        &Part::ALL,
+        |input, part| Ok::<_, Infallible>(solve(input, part)),
+    ) {
+        return;
+    }
+
+    let instructions = parse(&input);
+
+    println!("result: {}", part1(&instructions));
+    println!("conditional: {}", part2(&instructions));
 }
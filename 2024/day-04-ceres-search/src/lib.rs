@@ -0,0 +1,62 @@
+use aoc_core::Part;
+use aoc_grid::{Direction, Grid, ParseGridError, Point};
+use std::{fmt, iter};
+
+#[derive(Debug)]
+pub enum Error {
+    Map(ParseGridError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Map(err) => write!(f, "invalid word search at {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn find_word(grid: &Grid<char>, word: &str, p: Point, direction: Direction) -> bool {
+    let mut cells = iter::once(p).chain(grid.ray(p, direction)).map(|q| grid[q]);
+    word.chars().all(|c| cells.next() == Some(c))
+}
+
+fn is_x_mas(grid: &Grid<char>, p: Point) -> bool {
+    let diagonal = |a: Direction| {
+        matches!(
+            (grid.get(p + a), grid.get(p + a.opposite())),
+            (Some('M'), Some('S')) | (Some('S'), Some('M'))
+        )
+    };
+
+    grid[p] == 'A' && diagonal(Direction::NW) && diagonal(Direction::NE)
+}
+
+pub fn parse(input: &str) -> Result<Grid<char>, Error> {
+    input.parse().map_err(Error::Map)
+}
+
+pub fn part1(input: &Grid<char>) -> usize {
+    input
+        .points()
+        .map(|p| {
+            Direction::ALL
+                .into_iter()
+                .filter(|&d| find_word(input, "XMAS", p, d))
+                .count()
+        })
+        .sum()
+}
+
+pub fn part2(input: &Grid<char>) -> usize {
+    input.points().filter(|&p| is_x_mas(input, p)).count()
+}
+
+pub fn solve(input: &str, part: Part) -> Result<String, Error> {
+    let input = parse(input)?;
+    Ok(match part {
+        Part::One => part1(&input).to_string(),
+        Part::Two => part2(&input).to_string(),
+    })
+}
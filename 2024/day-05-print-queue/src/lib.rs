@@ -0,0 +1,119 @@
+use aoc_core::{
+    parse::{parse_line, tag, ParseError},
+    Part,
+};
+use nom::{character::complete::u32, multi::separated_list1, sequence::separated_pair};
+use std::{cmp::Ordering, fmt};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid rule or update at {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+fn is_valid(rules: Vec<(u32, u32)>) -> impl FnMut(&Vec<u32>) -> bool {
+    move |update| {
+        rules.iter().all(|(l, r)| {
+            match (
+                update.iter().position(|i| i == l),
+                update.iter().position(|i| i == r),
+            ) {
+                (None, _) => true,
+                (_, None) => true,
+                (Some(l), Some(r)) => l < r,
+            }
+        })
+    }
+}
+
+fn get_middle_items<T>(vec: Vec<Vec<T>>) -> Vec<T>
+where
+    T: Clone,
+{
+    vec.iter()
+        .map(|update| {
+            update
+                .get((update.len() as f32 / 2.0).floor() as usize)
+                .unwrap()
+        })
+        .cloned()
+        .collect()
+}
+
+pub struct Manual {
+    rules: Vec<(u32, u32)>,
+    updates: Vec<Vec<u32>>,
+}
+
+pub fn parse(input: &str) -> Result<Manual, Error> {
+    let (rules, updates) = input.split_once("\n\n").unwrap_or((input, ""));
+    let offset = rules.lines().count() + 1;
+    let rules: Vec<(u32, u32)> = rules
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(i, line, separated_pair(u32, tag("|"), u32)))
+        .collect::<Result<_, _>>()?;
+    let updates: Vec<Vec<u32>> = updates
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(offset + i, line, separated_list1(tag(","), u32)))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Manual { rules, updates })
+}
+
+pub fn part1(Manual { rules, updates }: &Manual) -> u32 {
+    let (valid_updates, _): (Vec<Vec<u32>>, Vec<Vec<u32>>) =
+        updates.iter().cloned().partition(is_valid(rules.clone()));
+    get_middle_items(valid_updates).iter().sum()
+}
+
+pub fn part2(Manual { rules, updates }: &Manual) -> u32 {
+    let (_, mut invalid_updates): (Vec<Vec<u32>>, Vec<Vec<u32>>) =
+        updates.iter().cloned().partition(is_valid(rules.clone()));
+    let fixed_invalid_updates: Vec<Vec<u32>> = invalid_updates
+        .iter_mut()
+        .map(|update| {
+            let mut fixed = update.clone();
+            fixed.sort_by(|a, b| {
+                if let Some(&(l, r)) = rules
+                    .iter()
+                    .find(|(l, r)| l == a && r == b || l == b && r == a)
+                {
+                    if l == *a && r == *b {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    }
+                } else {
+                    Ordering::Equal
+                }
+            });
+            fixed
+        })
+        .collect();
+    get_middle_items(fixed_invalid_updates).iter().sum()
+}
+
+pub fn solve(input: &str, part: Part) -> Result<String, Error> {
+    let manual = parse(input)?;
+    Ok(match part {
+        Part::One => part1(&manual).to_string(),
+        Part::Two => part2(&manual).to_string(),
+    })
+}
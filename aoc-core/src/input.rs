@@ -0,0 +1,73 @@
+use clap::Args;
+use std::{
+    fmt, fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+};
+
+#[derive(Args, Debug)]
+pub struct Input {
+    /// Puzzle input file [default: stdin, or input.txt when stdin is a terminal]
+    #[arg(value_name = "PATH", conflicts_with_all = ["input", "example"])]
+    path: Option<PathBuf>,
+    /// Puzzle input file
+    #[arg(long, value_name = "PATH", conflicts_with = "example")]
+    input: Option<PathBuf>,
+    /// Read the day's example.txt instead of its input.txt
+    #[arg(long, default_value_t = false)]
+    example: bool,
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    path: Option<PathBuf>,
+    source: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "failed to read {}: {}", path.display(), self.source),
+            None => write!(f, "failed to read stdin: {}", self.source),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+pub fn default_path(dir: impl AsRef<Path>, example: bool) -> PathBuf {
+    dir.as_ref()
+        .join(if example { "example.txt" } else { "input.txt" })
+}
+
+pub fn read_path(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|source| InputError {
+        path: Some(path.to_path_buf()),
+        source,
+    })
+}
+
+impl Input {
+    /// Whether the day's example was asked for.
+    pub fn is_example(&self) -> bool {
+        self.example
+    }
+
+    /// Reads the selected input, resolving default files relative to `dir`.
+    pub fn read(&self, dir: impl AsRef<Path>) -> Result<String, InputError> {
+        match self.path.as_ref().or(self.input.as_ref()) {
+            Some(path) => read_path(path),
+            None if self.example || io::stdin().is_terminal() => {
+                read_path(default_path(dir, self.example))
+            }
+            None => {
+                io::read_to_string(io::stdin()).map_err(|source| InputError { path: None, source })
+            }
+        }
+    }
+}
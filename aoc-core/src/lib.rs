@@ -0,0 +1,55 @@
+mod input;
+mod output;
+pub mod parse;
+
+pub use input::{default_path, read_path, Input, InputError};
+pub use output::{print_answers, puzzle, Format, Output, Report};
+
+use std::{fmt, process, str::FromStr};
+
+#[derive(Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd, Debug)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+#[derive(Debug)]
+pub struct ParsePartError(String);
+
+impl fmt::Display for ParsePartError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid part `{}`, expected 1 or 2", self.0)
+    }
+}
+
+impl std::error::Error for ParsePartError {}
+
+impl FromStr for Part {
+    type Err = ParsePartError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(ParsePartError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Part::One => "1",
+            Part::Two => "2",
+        })
+    }
+}
+
+pub fn fail(err: impl fmt::Display) -> ! {
+    eprintln!("error: {}", err);
+    process::exit(1);
+}
@@ -0,0 +1,156 @@
+use crate::Part;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use serde_json::Value;
+use std::{fmt, path::Path, time::Instant};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Args, Debug)]
+pub struct Output {
+    /// Print the answer as text, or both parts and the time taken as JSON
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+impl Output {
+    /// Under `--format json`, solves `parts` and prints the report, returning
+    /// whether it did so the caller can skip its text output.
+    pub fn report<E: fmt::Display>(
+        &self,
+        dir: impl AsRef<Path>,
+        input: &str,
+        parts: &[Part],
+        solve: impl Fn(&str, Part) -> Result<String, E>,
+    ) -> bool {
+        if self.format != Format::Json {
+            return false;
+        }
+        let report = Report::solve(dir, input, parts, solve).unwrap_or_else(|err| crate::fail(err));
+        println!("{}", report);
+        true
+    }
+}
+
+/// Reads the year and day from a package directory such as
+/// `2022/day-01-calorie-counting`.
+pub fn puzzle(dir: impl AsRef<Path>) -> Option<(u16, u8)> {
+    let dir = dir.as_ref();
+    let name = dir.file_name()?.to_str()?;
+    let day = name.strip_prefix("day-")?.split('-').next()?.parse().ok()?;
+    let year = dir.parent()?.file_name()?.to_str()?.parse().ok()?;
+    Some((year, day))
+}
+
+fn answer(answer: String) -> Value {
+    match answer.parse::<i64>() {
+        Ok(n) => Value::from(n),
+        Err(_) => match answer.parse::<u64>() {
+            Ok(n) => Value::from(n),
+            Err(_) => Value::String(answer),
+        },
+    }
+}
+
+/// Prints both answers, each labelled with its part.
+pub fn print_answers(part1: impl fmt::Display, part2: impl fmt::Display) {
+    for (part, answer) in [
+        (Part::One, part1.to_string()),
+        (Part::Two, part2.to_string()),
+    ] {
+        if answer.contains('\n') {
+            println!("part {}:\n{}", part, answer);
+        } else {
+            println!("part {}: {}", part, answer);
+        }
+    }
+}
+
+/// A day's answers in the shape emitted by `--format json`; numeric answers
+/// are numbers and parts that weren't solved are null.
+#[derive(Serialize, Debug)]
+pub struct Report {
+    pub year: u16,
+    pub day: u8,
+    pub part1: Option<Value>,
+    pub part2: Option<Value>,
+    pub elapsed_ms: f64,
+}
+
+impl Report {
+    /// Solves each of `parts` for the day whose package lives in `dir`.
+    pub fn solve<E>(
+        dir: impl AsRef<Path>,
+        input: &str,
+        parts: &[Part],
+        solve: impl Fn(&str, Part) -> Result<String, E>,
+    ) -> Result<Report, E> {
+        let dir = dir.as_ref();
+        let (year, day) = puzzle(dir)
+            .unwrap_or_else(|| panic!("{} is not a <year>/day-<dd>-* package", dir.display()));
+        let start = Instant::now();
+        let mut report = Report {
+            year,
+            day,
+            part1: None,
+            part2: None,
+            elapsed_ms: 0.0,
+        };
+        for &part in parts {
+            let result = Some(answer(solve(input, part)?));
+            match part {
+                Part::One => report.part1 = result,
+                Part::Two => report.part2 = result,
+            }
+        }
+        report.elapsed_ms = start.elapsed().as_micros() as f64 / 1000.0;
+        Ok(report)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        output::{puzzle, Report},
+        Part,
+    };
+    use std::convert::Infallible;
+
+    #[test]
+    fn identifies_packages() {
+        assert_eq!(puzzle("/src/2022/day-07-no-space"), Some((2022, 7)));
+        assert_eq!(puzzle("/src/aoc-core"), None);
+    }
+
+    #[test]
+    fn reports_numbers_and_text() {
+        let report = Report::solve("2022/day-10-cathode-ray-tube", "", &[Part::One], |_, _| {
+            Ok::<_, Infallible>("13140".to_string())
+        })
+        .unwrap();
+        assert_eq!(report.part1, Some(13140.into()));
+        assert_eq!(report.part2, None);
+        let report = Report::solve("2022/day-05-supply-stacks", "", &Part::ALL, |_, part| {
+            Ok::<_, Infallible>(format!("CMZ\n{}", part))
+        })
+        .unwrap();
+        let json = report.to_string();
+        assert!(json.starts_with(
+            r#"{"year":2022,"day":5,"part1":"CMZ\n1","part2":"CMZ\n2","elapsed_ms":"#
+        ));
+    }
+}
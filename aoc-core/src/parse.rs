@@ -0,0 +1,278 @@
+use nom::{
+    error::{ContextError, ErrorKind, FromExternalError},
+    Parser,
+};
+use std::fmt;
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, Error<'a>>;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Token {
+    Literal(&'static str),
+    Named(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Literal(literal) => write!(f, "{:?}", literal),
+            Token::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// The nom error type used by every day's parser: where it failed and which
+/// tokens would have been accepted there.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Error<'a> {
+    pub input: &'a str,
+    pub expected: Vec<Token>,
+}
+
+impl<'a> Error<'a> {
+    pub fn new(input: &'a str, expected: Token) -> Self {
+        Error {
+            input,
+            expected: vec![expected],
+        }
+    }
+}
+
+fn describe(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Digit => "digits",
+        ErrorKind::Alpha => "letters",
+        ErrorKind::AlphaNumeric => "letters or digits",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "line ending",
+        ErrorKind::Char => "character",
+        ErrorKind::Eof => "end of input",
+        ErrorKind::MapRes => "number",
+        ErrorKind::Tag => "literal",
+        _ => "valid input",
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Error::new(input, Token::Named(describe(kind)))
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn or(mut self, other: Self) -> Self {
+        match self.input.len().cmp(&other.input.len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => {
+                for token in other.expected {
+                    if !self.expected.contains(&token) {
+                        self.expected.push(token);
+                    }
+                }
+                self
+            }
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    fn add_context(input: &'a str, context: &'static str, other: Self) -> Self {
+        if other.input.len() == input.len() {
+            Error::new(input, Token::Named(context))
+        } else {
+            other
+        }
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _: E) -> Self {
+        Error::new(input, Token::Named(describe(kind)))
+    }
+}
+
+/// A drop-in for nom's `tag` that reports the literal it was looking for.
+pub fn tag<'a>(literal: &'static str) -> impl Fn(&'a str) -> IResult<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(literal) {
+        Some(rest) => Ok((rest, &input[..literal.len()])),
+        None => Err(nom::Err::Error(Error::new(input, Token::Literal(literal)))),
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    /// Locates `rest`, which must be a suffix of `input`.
+    pub fn of(input: &str, rest: &str) -> Self {
+        let consumed = &input[..input.len() - rest.len()];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        Location {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ParseError {
+    pub location: Location,
+    pub expected: Vec<Token>,
+    pub found: Option<String>,
+}
+
+impl ParseError {
+    fn new(input: &str, error: Error) -> Self {
+        let line = error.input.lines().next().unwrap_or("");
+        let found = line
+            .split_whitespace()
+            .next()
+            .unwrap_or(line)
+            .chars()
+            .take(16)
+            .collect::<String>();
+        ParseError {
+            location: Location::of(input, error.input),
+            expected: error.expected,
+            found: (!found.is_empty()).then_some(found),
+        }
+    }
+
+    /// Moves a location relative to a single line onto `line` (zero-based).
+    fn on_line(mut self, line: usize) -> Self {
+        self.location.line += line;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: expected ", self.location)?;
+        for (i, token) in self.expected.iter().enumerate() {
+            match i {
+                0 => {}
+                i if i == self.expected.len() - 1 => write!(f, " or ")?,
+                _ => write!(f, ", ")?,
+            }
+            write!(f, "{}", token)?;
+        }
+        match &self.found {
+            Some(found) => write!(f, ", found {:?}", found),
+            None => write!(f, ", found end of line"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs `parser` over the whole of `input`, with trailing whitespace removed.
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, O, Error<'a>>,
+) -> Result<O, ParseError> {
+    let input = input.trim_end();
+    match parser.parse(input) {
+        Ok(("", output)) => Ok(output),
+        Ok((rest, _)) => Err(ParseError::new(
+            input,
+            Error::new(rest, Token::Named("end of input")),
+        )),
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+            Err(ParseError::new(input, error))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::new(
+            input,
+            Error::new("", Token::Named("more input")),
+        )),
+    }
+}
+
+/// Runs `parser` over a single line, reporting errors against line `index`
+/// (zero-based) of the surrounding input.
+pub fn parse_line<'a, O>(
+    index: usize,
+    line: &'a str,
+    mut parser: impl Parser<&'a str, O, Error<'a>>,
+) -> Result<O, ParseError> {
+    match parser.parse(line) {
+        Ok(("", output)) => Ok(output),
+        Ok((rest, _)) => {
+            Err(ParseError::new(line, Error::new(rest, Token::Named("end of line"))).on_line(index))
+        }
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+            Err(ParseError::new(line, error).on_line(index))
+        }
+        Err(nom::Err::Incomplete(_)) => {
+            Err(ParseError::new(line, Error::new("", Token::Named("more input"))).on_line(index))
+        }
+    }
+}
+
+/// Parses every line of `input` with `parser`.
+pub fn parse_lines<'a, O>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, O, Error<'a>>,
+) -> Result<Vec<O>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(i, line, |line| parser.parse(line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse_all, parse_lines, tag, Location, Token};
+    use nom::{branch::alt, character::complete::digit1, sequence::preceded};
+
+    #[test]
+    fn locates_suffixes() {
+        let input = "ab\ncde\nf";
+        assert_eq!(
+            Location::of(input, &input[5..]),
+            Location { line: 2, column: 3 }
+        );
+        assert_eq!(Location::of(input, ""), Location { line: 3, column: 2 });
+    }
+
+    #[test]
+    fn reports_expected_tokens() {
+        let err = parse_lines("add 1\nsub 2\nmul 3", |i| {
+            alt((preceded(tag("add "), digit1), preceded(tag("sub "), digit1)))(i)
+        })
+        .unwrap_err();
+        assert_eq!(err.location, Location { line: 3, column: 1 });
+        assert_eq!(
+            err.expected,
+            vec![Token::Literal("add "), Token::Literal("sub ")]
+        );
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: expected \"add \" or \"sub \", found \"mul\""
+        );
+    }
+
+    #[test]
+    fn rejects_trailing_input() {
+        let err = parse_all("12\n3x\n", |i| {
+            nom::multi::separated_list1(tag("\n"), digit1)(i)
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected end of input, found \"x\""
+        );
+    }
+}
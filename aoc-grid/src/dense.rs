@@ -0,0 +1,205 @@
+use crate::{Direction, Point};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum ParseGridError {
+    Ragged { row: usize },
+    InvalidCell { x: usize, y: usize, c: char },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseGridError::Ragged { row } => {
+                write!(f, "line {}: row has a different width", row + 1)
+            }
+            ParseGridError::InvalidCell { x, y, c } => {
+                write!(f, "line {}, column {}: unexpected {:?}", y + 1, x + 1, c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn parse_with<E>(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseGridError> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).map_err(|_| ParseGridError::InvalidCell { x, y, c })?);
+            }
+            match width {
+                None => width = Some(cells.len() - start),
+                Some(width) if width != cells.len() - start => {
+                    return Err(ParseGridError::Ragged { row: y })
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x() >= 0 && p.y() >= 0 && (p.x() as usize) < self.width && (p.y() as usize) < self.height
+    }
+
+    fn idx(&self, p: Point) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y() as usize * self.width + p.x() as usize)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.idx(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.idx(p).map(|i| &mut self.cells[i])
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x as i64, y as i64)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter().find_map(|(p, t)| predicate(t).then_some(p))
+    }
+
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.von_neumann().filter(|&n| self.contains(n))
+    }
+
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.moore().filter(|&n| self.contains(n))
+    }
+
+    pub fn ray(&self, from: Point, direction: Direction) -> impl Iterator<Item = Point> + '_ {
+        let mut p = from;
+        std::iter::from_fn(move || {
+            p += direction;
+            self.contains(p).then_some(p)
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", p))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", p))
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, T::try_from)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for t in row {
+                write!(f, "{}", t)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Direction, Grid, ParseGridError, Point};
+
+    #[test]
+    fn parses_and_renders() {
+        let grid: Grid<char> = "ab\ncd".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[Point::new(1, 0)], 'b');
+        assert_eq!(grid.to_string(), "ab\ncd");
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>(),
+            Err(ParseGridError::Ragged { row: 1 })
+        );
+    }
+
+    #[test]
+    fn checks_neighbour_bounds() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbours4(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn casts_rays() {
+        let grid = Grid::new(4, 4, 0);
+        let ray: Vec<Point> = grid.ray(Point::new(1, 1), Direction::SE).collect();
+        assert_eq!(ray, vec![Point::new(2, 2), Point::new(3, 3)]);
+        assert_eq!(grid.ray(Point::new(0, 1), Direction::W).count(), 0);
+    }
+}
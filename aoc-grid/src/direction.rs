@@ -0,0 +1,53 @@
+use crate::Point;
+
+#[derive(Copy, Clone, Eq, Hash, PartialEq, Debug)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::N => Point::new(0, -1),
+            Direction::NE => Point::new(1, -1),
+            Direction::E => Point::new(1, 0),
+            Direction::SE => Point::new(1, 1),
+            Direction::S => Point::new(0, 1),
+            Direction::SW => Point::new(-1, 1),
+            Direction::W => Point::new(-1, 0),
+            Direction::NW => Point::new(-1, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::N => Direction::S,
+            Direction::NE => Direction::SW,
+            Direction::E => Direction::W,
+            Direction::SE => Direction::NW,
+            Direction::S => Direction::N,
+            Direction::SW => Direction::NE,
+            Direction::W => Direction::E,
+            Direction::NW => Direction::SE,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+mod dense;
+mod direction;
+mod sparse;
+
+pub use dense::{Grid, ParseGridError};
+pub use direction::Direction;
+pub use sparse::SparseGrid;
+
+use std::ops::{Add, AddAssign};
+
+pub type Point = aoc_point::Point<i64, 2>;
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Point {
+        self + rhs.offset()
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
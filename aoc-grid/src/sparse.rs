@@ -0,0 +1,134 @@
+use crate::{ParseGridError, Point};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn insert(&mut self, p: Point, t: T) -> Option<T> {
+        self.cells.insert(p, t)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, t)| (p, t))
+    }
+
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.points().fold(None, |bounds, p| match bounds {
+            None => Some((p, p)),
+            Some((min, max)) => Some((
+                Point::new(min.x().min(p.x()), min.y().min(p.y())),
+                Point::new(max.x().max(p.x()), max.y().max(p.y())),
+            )),
+        })
+    }
+
+    pub fn occupied_neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.von_neumann().filter(|&n| self.contains(n))
+    }
+
+    pub fn occupied_neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.moore().filter(|&n| self.contains(n))
+    }
+
+    pub fn render(&self, min: Point, max: Point, empty: char) -> String
+    where
+        T: fmt::Display,
+    {
+        (min.y()..=max.y())
+            .map(|y| {
+                (min.x()..=max.x())
+                    .map(|x| match self.get(Point::new(x, y)) {
+                        Some(t) => t.to_string(),
+                        None => empty.to_string(),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl FromStr for SparseGrid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cells = s.lines().enumerate().flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, c)| c != '.')
+                .map(move |(x, c)| (Point::new(x as i64, y as i64), c))
+        });
+
+        Ok(cells.collect())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.bounds() {
+            Some((min, max)) => write!(f, "{}", self.render(min, max, '.')),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, SparseGrid};
+
+    #[test]
+    fn renders_bounding_box() {
+        let grid: SparseGrid<char> = "...\n.#.\n..#".parse().unwrap();
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.bounds(), Some((Point::new(1, 1), Point::new(2, 2))));
+        assert_eq!(grid.to_string(), "#.\n.#");
+        assert_eq!(grid.occupied_neighbours8(Point::new(1, 1)).count(), 1);
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A route from one of a search's starts to the node it found, and what it
+/// costs to follow.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+impl<N, C> Path<N, C> {
+    pub fn start(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    pub fn end(&self) -> &N {
+        &self.nodes[self.nodes.len() - 1]
+    }
+
+    /// The number of moves along the path.
+    pub fn len(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Every node a search reached, with the cheapest known cost and the node
+/// it was reached from.
+#[derive(Clone, Debug)]
+pub struct Reached<N, C> {
+    nodes: HashMap<N, (C, Option<N>)>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Reached<N, C> {
+    fn new() -> Self {
+        Reached {
+            nodes: HashMap::new(),
+        }
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.nodes.get(node).map(|&(cost, _)| cost)
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.nodes.iter().map(|(node, &(cost, _))| (node, cost))
+    }
+
+    /// Follows the nodes each was reached from back to a start.
+    pub fn path(&self, node: &N) -> Option<Path<N, C>> {
+        let cost = self.cost(node)?;
+        let mut nodes = vec![node.clone()];
+        while let Some((_, Some(previous))) = self.nodes.get(&nodes[nodes.len() - 1]) {
+            nodes.push(previous.clone());
+        }
+        nodes.reverse();
+        Some(Path { nodes, cost })
+    }
+}
+
+fn breadth_first<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Reached<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = reached.nodes.entry(start.clone()) {
+            entry.insert((0, None));
+            queue.push_back((start, 0));
+        }
+    }
+    while let Some((node, distance)) = queue.pop_front() {
+        if goal(&node) {
+            return (reached, Some(node));
+        }
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = reached.nodes.entry(next.clone()) {
+                entry.insert((distance + 1, Some(node.clone())));
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    (reached, None)
+}
+
+fn best_first<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Reached<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut reached = Reached::new();
+    // The heap orders indices into `pending` so nodes needn't be `Ord`.
+    let mut pending = Vec::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = reached.nodes.entry(start.clone()) {
+            entry.insert((C::default(), None));
+            heap.push(Reverse((heuristic(&start), pending.len())));
+            pending.push((start, C::default()));
+        }
+    }
+    while let Some(Reverse((_, i))) = heap.pop() {
+        let (node, cost) = pending[i].clone();
+        if reached.nodes[&node].0 < cost {
+            continue;
+        }
+        if goal(&node) {
+            return (reached, Some(node));
+        }
+        for (next, step) in neighbours(&node) {
+            let cost = cost + step;
+            match reached.nodes.entry(next.clone()) {
+                Entry::Occupied(entry) if entry.get().0 <= cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert((cost, Some(node.clone())));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((cost, Some(node.clone())));
+                }
+            }
+            heap.push(Reverse((cost + heuristic(&next), pending.len())));
+            pending.push((next, cost));
+        }
+    }
+    (reached, None)
+}
+
+/// The shortest path in moves from any of `starts` to a node satisfying
+/// `goal`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (reached, found) = breadth_first(starts, neighbours, goal);
+    reached.path(&found?)
+}
+
+/// Every node reachable from `starts`, with its distance in moves.
+pub fn bfs_reach<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+) -> Reached<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    breadth_first(starts, neighbours, |_| false).0
+}
+
+/// The cheapest path from any of `starts` to a node satisfying `goal`,
+/// where `neighbours` gives each next node with the cost of moving there.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (reached, found) = best_first(starts, neighbours, |_| C::default(), goal);
+    reached.path(&found?)
+}
+
+/// Every node reachable from `starts`, with the cheapest cost of getting
+/// there.
+pub fn dijkstra_reach<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+) -> Reached<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, neighbours, |_| C::default(), |_| false).0
+}
+
+/// Like [`dijkstra`], but guided by `heuristic`, an estimate of the cost
+/// left to reach a goal. The path is the cheapest as long as the estimate
+/// never exceeds the true cost.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (reached, found) = best_first(starts, neighbours, heuristic, goal);
+    reached.path(&found?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{astar, bfs, bfs_reach, dijkstra};
+
+    // 0 - 1 - 2 - 3, with a costly shortcut 0 - 3 and a dead end at 4.
+    fn neighbours(&n: &u32) -> Vec<(u32, u32)> {
+        match n {
+            0 => vec![(1, 1), (3, 5)],
+            1 => vec![(0, 1), (2, 1)],
+            2 => vec![(1, 1), (3, 1)],
+            3 => vec![(2, 1), (0, 5)],
+            _ => vec![],
+        }
+    }
+
+    fn unweighted(n: &u32) -> Vec<u32> {
+        neighbours(n).into_iter().map(|(n, _)| n).collect()
+    }
+
+    #[test]
+    fn breadth_first_counts_moves() {
+        let path = bfs([0], unweighted, |&n| n == 3).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec![0, 3], 1));
+        assert_eq!(bfs([0], unweighted, |&n| n == 4), None);
+        let reached = bfs_reach([2], unweighted);
+        assert_eq!((reached.len(), reached.cost(&0)), (4, Some(2)));
+        assert_eq!(reached.path(&0).unwrap().nodes, vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn weighted_searches_find_the_cheapest_path() {
+        let path = dijkstra([0], neighbours, |&n| n == 3).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec![0, 1, 2, 3], 3));
+        let path = astar([0], neighbours, |&n| 3 - n.min(3), |&n| n == 3).unwrap();
+        assert_eq!(path.cost, 3);
+    }
+
+    #[test]
+    fn searches_from_the_nearest_start() {
+        let path = bfs([0, 2], unweighted, |&n| n == 1).unwrap();
+        assert_eq!(path.cost, 1);
+        let path = dijkstra([1, 3], neighbours, |&n| n == 0).unwrap();
+        assert_eq!((*path.start(), path.cost), (1, 1));
+    }
+}
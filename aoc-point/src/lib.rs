@@ -0,0 +1,248 @@
+use num_traits::{Signed, ToPrimitive};
+use std::{
+    array,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+};
+
+#[derive(Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd, Debug)]
+pub struct Point<T, const N: usize>(pub [T; N]);
+
+pub type Point2<T> = Point<T, 2>;
+pub type Point3<T> = Point<T, 3>;
+
+impl<T> Point<T, 2> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point([x, y])
+    }
+}
+
+impl<T: Copy> Point<T, 2> {
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+}
+
+impl<T> Point<T, 3> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point([x, y, z])
+    }
+}
+
+impl<T: Copy> Point<T, 3> {
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> T {
+        self.0[2]
+    }
+}
+
+impl<T: Copy, const N: usize> Point<T, N> {
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    fn map(self, f: impl Fn(T) -> T) -> Self {
+        Point(self.0.map(f))
+    }
+
+    fn zip(self, other: Self, f: impl Fn(T, T) -> T) -> Self {
+        Point(array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+}
+
+impl<T: Signed + Copy + PartialOrd, const N: usize> Point<T, N> {
+    pub fn origin() -> Self {
+        Point([T::zero(); N])
+    }
+
+    pub fn abs(self) -> Self {
+        self.map(|t| t.abs())
+    }
+
+    pub fn signum(self) -> Self {
+        self.map(|t| t.signum())
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        (*self - *other)
+            .0
+            .into_iter()
+            .fold(T::zero(), |sum, t| sum + t.abs())
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        (*self - *other).0.into_iter().fold(
+            T::zero(),
+            |max, t| if t.abs() > max { t.abs() } else { max },
+        )
+    }
+
+    pub fn euclidean(&self, other: &Self) -> f64
+    where
+        T: ToPrimitive,
+    {
+        (*self - *other)
+            .0
+            .into_iter()
+            .map(|t| t.to_f64().unwrap().powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Every offset in {-1, 0, 1}^N except the origin, in a fixed order.
+    fn offsets() -> impl Iterator<Item = Self> {
+        (1..3usize.pow(N as u32)).map(|mut i| {
+            Point(array::from_fn(|_| {
+                let digit = i % 3;
+                i /= 3;
+                match digit {
+                    0 => T::zero(),
+                    1 => T::one(),
+                    _ => -T::one(),
+                }
+            }))
+        })
+    }
+
+    /// The 2N orthogonally adjacent points.
+    pub fn von_neumann(self) -> impl Iterator<Item = Self> {
+        Self::offsets()
+            .filter(|offset| offset.manhattan(&Self::origin()) == T::one())
+            .map(move |offset| self + offset)
+    }
+
+    /// The 3^N - 1 points that touch this one, diagonals included.
+    pub fn moore(self) -> impl Iterator<Item = Self> {
+        Self::offsets().map(move |offset| self + offset)
+    }
+}
+
+// Quarter turns on a map whose y axis points down, so `rotate_left` turns a
+// heading of north into west.
+impl<T: Signed + Copy> Point<T, 2> {
+    pub fn rotate_left(self) -> Self {
+        Point::<T, 2>::new(self.y(), -self.x())
+    }
+
+    pub fn rotate_right(self) -> Self {
+        Point::<T, 2>::new(-self.y(), self.x())
+    }
+}
+
+// Quarter turns about each axis, counter-clockwise when looking back towards
+// the origin from the positive end of that axis.
+impl<T: Signed + Copy> Point<T, 3> {
+    pub fn rotate_x(self) -> Self {
+        Point::<T, 3>::new(self.x(), -self.z(), self.y())
+    }
+
+    pub fn rotate_y(self) -> Self {
+        Point::<T, 3>::new(self.z(), self.y(), -self.x())
+    }
+
+    pub fn rotate_z(self) -> Self {
+        Point::<T, 3>::new(-self.y(), self.x(), self.z())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Point<T, N> {
+    fn from(coords: [T; N]) -> Self {
+        Point(coords)
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Point<T, N> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        &self.0[i]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Point<T, N> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        &mut self.0[i]
+    }
+}
+
+impl<T: Add<Output = T> + Copy, const N: usize> Add for Point<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.zip(rhs, T::add)
+    }
+}
+
+impl<T: Add<Output = T> + Copy, const N: usize> AddAssign for Point<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy, const N: usize> Sub for Point<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.zip(rhs, T::sub)
+    }
+}
+
+impl<T: Sub<Output = T> + Copy, const N: usize> SubAssign for Point<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Neg<Output = T> + Copy, const N: usize> Neg for Point<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(T::neg)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy, const N: usize> Mul<T> for Point<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        self.map(|t| t * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point2, Point3};
+
+    #[test]
+    fn measures_distances() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(4, 2);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.euclidean(&b), 5.0);
+        assert_eq!((b - a).signum(), Point2::new(1, 1));
+        assert_eq!(-a * 2, Point2::new(-2, 4));
+    }
+
+    #[test]
+    fn rotates() {
+        let north = Point2::new(0, -1);
+        assert_eq!(north.rotate_left(), Point2::new(-1, 0));
+        assert_eq!(north.rotate_right(), Point2::new(1, 0));
+        assert_eq!(north.rotate_left().rotate_right(), north);
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(p.rotate_z().rotate_z().rotate_z().rotate_z(), p);
+        assert_eq!(p.rotate_x(), Point3::new(1, -3, 2));
+    }
+
+    #[test]
+    fn iterates_neighbourhoods() {
+        let p = Point3::new(0, 0, 0);
+        assert_eq!(p.von_neumann().count(), 6);
+        assert_eq!(p.moore().count(), 26);
+        assert!(p.von_neumann().all(|q| q.manhattan(&p) == 1));
+        assert_eq!(Point2::new(5, 5).moore().count(), 8);
+    }
+}
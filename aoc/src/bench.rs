@@ -0,0 +1,224 @@
+use aoc_core::Part;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{
+    error::Error,
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Timings for `runs` separate parses of one input and a solve of each part.
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub parts: Vec<(Part, Vec<Duration>)>,
+}
+
+/// Times `parse` and then `solve` for each of `parts`, `runs` times over.
+pub fn measure<'a, T, E: Error + 'static>(
+    input: &'a str,
+    parts: &[Part],
+    runs: usize,
+    parse: impl Fn(&'a str) -> Result<T, E>,
+    solve: impl Fn(&T, Part),
+) -> Result<Samples, Box<dyn Error>> {
+    let mut samples = Samples {
+        parse: Vec::with_capacity(runs),
+        parts: parts
+            .iter()
+            .map(|&part| (part, Vec::with_capacity(runs)))
+            .collect(),
+    };
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = parse(black_box(input))?;
+        samples.parse.push(start.elapsed());
+        for (part, durations) in samples.parts.iter_mut() {
+            let start = Instant::now();
+            solve(&parsed, *part);
+            durations.push(start.elapsed());
+        }
+    }
+    Ok(samples)
+}
+
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn of(durations: &[Duration]) -> Self {
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        // Nearest-rank percentile.
+        let rank = |p: usize| sorted[(sorted.len() * p).div_ceil(100).max(1) - 1];
+        match sorted.first() {
+            Some(&min) => Stats {
+                min,
+                median: rank(50),
+                p95: rank(95),
+            },
+            None => Stats::default(),
+        }
+    }
+}
+
+impl std::ops::Add for Stats {
+    type Output = Stats;
+
+    fn add(self, other: Stats) -> Stats {
+        Stats {
+            min: self.min + other.min,
+            median: self.median + other.median,
+            p95: self.p95 + other.p95,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+    Total,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Part(part) => f.pad(&format!("part{}", part)),
+            Phase::Total => f.pad("total"),
+        }
+    }
+}
+
+/// One line of the report; a year's total has no day.
+pub struct Row {
+    pub year: u16,
+    pub day: Option<u8>,
+    pub phase: Phase,
+    pub runs: usize,
+    pub stats: Stats,
+}
+
+#[derive(Serialize)]
+struct Record {
+    year: u16,
+    day: Option<u8>,
+    phase: String,
+    runs: usize,
+    min_ns: u128,
+    median_ns: u128,
+    p95_ns: u128,
+}
+
+impl From<&Row> for Record {
+    fn from(row: &Row) -> Self {
+        Record {
+            year: row.year,
+            day: row.day,
+            phase: row.phase.to_string(),
+            runs: row.runs,
+            min_ns: row.stats.min.as_nanos(),
+            median_ns: row.stats.median.as_nanos(),
+            p95_ns: row.stats.p95.as_nanos(),
+        }
+    }
+}
+
+pub fn rows(year: u16, day: u8, runs: usize, samples: &Samples) -> Vec<Row> {
+    let row = |phase, durations: &[Duration]| Row {
+        year,
+        day: Some(day),
+        phase,
+        runs,
+        stats: Stats::of(durations),
+    };
+    let mut rows = vec![row(Phase::Parse, &samples.parse)];
+    for (part, durations) in &samples.parts {
+        rows.push(row(Phase::Part(*part), durations));
+    }
+    rows
+}
+
+/// Sums every day's rows into a total for the year.
+pub fn total(year: u16, runs: usize, rows: &[Row]) -> Row {
+    Row {
+        year,
+        day: None,
+        phase: Phase::Total,
+        runs,
+        stats: rows
+            .iter()
+            .filter(|row| row.year == year)
+            .fold(Stats::default(), |sum, row| sum + row.stats),
+    }
+}
+
+pub fn report(rows: &[Row], format: Format) -> String {
+    match format {
+        Format::Table => {
+            let mut lines = vec![format!(
+                "{:<4}  {:>3}  {:<5}  {:>10}  {:>10}  {:>10}",
+                "year", "day", "phase", "min", "median", "p95"
+            )];
+            lines.extend(rows.iter().map(|row| {
+                format!(
+                    "{:<4}  {:>3}  {:<5}  {:>10}  {:>10}  {:>10}",
+                    row.year,
+                    row.day.map_or(String::new(), |day| format!("{:02}", day)),
+                    row.phase,
+                    format!("{:.1?}", row.stats.min),
+                    format!("{:.1?}", row.stats.median),
+                    format!("{:.1?}", row.stats.p95)
+                )
+            }));
+            lines.join("\n")
+        }
+        Format::Json => {
+            let records: Vec<Record> = rows.iter().map(Record::from).collect();
+            serde_json::to_string_pretty(&records).unwrap()
+        }
+        Format::Csv => {
+            let mut lines = vec!["year,day,phase,runs,min_ns,median_ns,p95_ns".to_string()];
+            lines.extend(rows.iter().map(Record::from).map(|record| {
+                format!(
+                    "{},{},{},{},{},{},{}",
+                    record.year,
+                    record.day.map_or(String::new(), |day| day.to_string()),
+                    record.phase,
+                    record.runs,
+                    record.min_ns,
+                    record.median_ns,
+                    record.p95_ns
+                )
+            }));
+            lines.join("\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::Stats;
+    use std::time::Duration;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let durations: Vec<Duration> = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::of(&durations);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+    }
+}
@@ -0,0 +1,248 @@
+use crate::bench::{self, Samples};
+use aoc_core::Part;
+use std::{convert::Infallible, error::Error, hint::black_box};
+
+pub type Solve = fn(&str, Part) -> Result<String, Box<dyn Error>>;
+pub type Bench = fn(&str, &[Part], usize) -> Result<Samples, Box<dyn Error>>;
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub path: &'static str,
+    pub parts: &'static [Part],
+    pub solve: Solve,
+    pub bench: Bench,
+}
+
+impl Day {
+    pub fn name(&self) -> &'static str {
+        self.path.rsplit('/').next().unwrap_or(self.path)
+    }
+}
+
+/// Times a day's `parse` and `part1`/`part2`; `?` marks a fallible `parse`.
+macro_rules! bench {
+    ($day:ident) => {
+        bench!(@measure $day, |input| Ok::<_, Infallible>($day::parse(input)))
+    };
+    ($day:ident?) => {
+        bench!(@measure $day, $day::parse)
+    };
+    (@measure $day:ident, $parse:expr) => {
+        |input, parts, runs| {
+            bench::measure(input, parts, runs, $parse, |parsed, part| match part {
+                Part::One => {
+                    let _ = black_box($day::part1(parsed));
+                }
+                Part::Two => {
+                    let _ = black_box($day::part2(parsed));
+                }
+            })
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        year: 2022,
+        day: 1,
+        path: "2022/day-01-calorie-counting",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_01_calorie_counting::solve_part(input, part)),
+        bench: bench!(day_01_calorie_counting),
+    },
+    Day {
+        year: 2022,
+        day: 2,
+        path: "2022/day-02-rock-paper-scissors",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_02_rock_paper_scissors::solve_part(input, part)),
+        bench: bench!(day_02_rock_paper_scissors),
+    },
+    Day {
+        year: 2022,
+        day: 3,
+        path: "2022/day-03-rucksack-reorganization",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_03_rucksack_reorganization::solve_part(input, part)),
+        bench: bench!(day_03_rucksack_reorganization),
+    },
+    Day {
+        year: 2022,
+        day: 4,
+        path: "2022/day-04-camp-cleanup",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_04_camp_cleanup::solve_part(input, part)?),
+        bench: bench!(day_04_camp_cleanup?),
+    },
+    Day {
+        year: 2022,
+        day: 5,
+        path: "2022/day-05-supply-stacks",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_05_supply_stacks::solve_part(input, part)?),
+        bench: bench!(day_05_supply_stacks?),
+    },
+    Day {
+        year: 2022,
+        day: 6,
+        path: "2022/day-06-tuning-trouble",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_06_tuning_trouble::solve_part(input, part)?),
+        bench: bench!(day_06_tuning_trouble),
+    },
+    Day {
+        year: 2022,
+        day: 7,
+        path: "2022/day-07-no-space-left-on-device",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_07_no_space_left_on_device::solve_part(input, part)?),
+        bench: bench!(day_07_no_space_left_on_device?),
+    },
+    Day {
+        year: 2022,
+        day: 8,
+        path: "2022/day-08-treetop-tree-house",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_08_treetop_tree_house::solve_part(input, part)?),
+        bench: bench!(day_08_treetop_tree_house?),
+    },
+    Day {
+        year: 2022,
+        day: 9,
+        path: "2022/day-09-rope-bridge",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_09_rope_bridge::solve_part(input, part)?),
+        bench: bench!(day_09_rope_bridge?),
+    },
+    Day {
+        year: 2022,
+        day: 10,
+        path: "2022/day-10-cathode-ray-tube",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_10_cathode_ray_tube::solve_part(input, part)?),
+        bench: bench!(day_10_cathode_ray_tube?),
+    },
+    Day {
+        year: 2022,
+        day: 11,
+        path: "2022/day-11-monkey-in-the-middle",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_11_monkey_in_the_middle::solve_part(input, part)?),
+        bench: bench!(day_11_monkey_in_the_middle?),
+    },
+    Day {
+        year: 2022,
+        day: 12,
+        path: "2022/day-12-hill-climbing-algorithm",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_12_hill_climbing_algorithm::solve_part(input, part)?),
+        bench: bench!(day_12_hill_climbing_algorithm?),
+    },
+    Day {
+        year: 2022,
+        day: 13,
+        path: "2022/day-13-distress-signal",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_13_distress_signal::solve_part(input, part)?),
+        bench: bench!(day_13_distress_signal?),
+    },
+    Day {
+        year: 2022,
+        day: 14,
+        path: "2022/day-14-regolith-reservoir",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_14_regolith_reservoir::solve_part(input, part)?),
+        bench: bench!(day_14_regolith_reservoir?),
+    },
+    Day {
+        year: 2022,
+        day: 15,
+        path: "2022/day-15-beacon-exclusion-zone",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_15_beacon_exclusion_zone::solve_part(input, part)?),
+        bench: bench!(day_15_beacon_exclusion_zone?),
+    },
+    Day {
+        year: 2022,
+        day: 18,
+        path: "2022/day-18-boiling-boulders",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_18_boiling_boulders::solve_part(input, part)?),
+        bench: bench!(day_18_boiling_boulders?),
+    },
+    Day {
+        year: 2022,
+        day: 20,
+        path: "2022/day-20-grove-positioning-system",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_20_grove_positioning_system::solve_part(input, part)?),
+        bench: bench!(day_20_grove_positioning_system?),
+    },
+    Day {
+        year: 2022,
+        day: 23,
+        path: "2022/day-23-unstable-diffusion",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_23_unstable_diffusion::solve_part(input, part)),
+        bench: bench!(day_23_unstable_diffusion),
+    },
+    Day {
+        year: 2022,
+        day: 25,
+        path: "2022/day-25-full-of-hot-air",
+        parts: &[Part::One],
+        solve: |input, part| Ok(day_25_full_of_hot_air::solve_part(input, part)?),
+        bench: |input, parts, runs| {
+            bench::measure(
+                input,
+                parts,
+                runs,
+                |input| Ok::<_, Infallible>(day_25_full_of_hot_air::parse(input)),
+                |parsed, _| {
+                    black_box(day_25_full_of_hot_air::part1(parsed));
+                },
+            )
+        },
+    },
+    Day {
+        year: 2024,
+        day: 1,
+        path: "2024/day-01-historian-hysteria",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_01_historian_hysteria::solve(input, part)?),
+        bench: bench!(day_01_historian_hysteria?),
+    },
+    Day {
+        year: 2024,
+        day: 2,
+        path: "2024/day-02-red-nosed-reports",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_02_red_nosed_reports::solve(input, part)?),
+        bench: bench!(day_02_red_nosed_reports?),
+    },
+    Day {
+        year: 2024,
+        day: 3,
+        path: "2024/day-03-mull-it-over",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_03_mull_it_over::solve(input, part)),
+        bench: bench!(day_03_mull_it_over),
+    },
+    Day {
+        year: 2024,
+        day: 4,
+        path: "2024/day-04-ceres-search",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_04_ceres_search::solve(input, part)?),
+        bench: bench!(day_04_ceres_search?),
+    },
+    Day {
+        year: 2024,
+        day: 5,
+        path: "2024/day-05-print-queue",
+        parts: &Part::ALL,
+        solve: |input, part| Ok(day_05_print_queue::solve(input, part)?),
+        bench: bench!(day_05_print_queue?),
+    },
+];
@@ -0,0 +1,273 @@
+mod bench;
+mod days;
+mod verify;
+
+use aoc_core::{Part, Report};
+use bench::{Format, Row};
+use clap::{ArgGroup, Parser, Subcommand};
+use days::{Day, DAYS};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process,
+};
+use verify::Status;
+
+#[derive(Parser)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, or every day of a year with --all
+    #[command(group(ArgGroup::new("days").required(true).args(["day", "all"])))]
+    Run {
+        year: u16,
+        day: Option<u8>,
+        #[arg(long)]
+        part: Option<Part>,
+        #[arg(long, default_value_t = false)]
+        all: bool,
+        /// Use each day's example.txt instead of its input.txt
+        #[arg(long, default_value_t = false)]
+        example: bool,
+        /// Print answers as text, or one JSON object per day
+        #[arg(long, value_enum, default_value_t = aoc_core::Format::Text)]
+        format: aoc_core::Format,
+    },
+    /// Check answers against each year's answers.toml
+    Verify { year: Option<u16>, day: Option<u8> },
+    /// Time each day's parse and parts separately over several runs
+    Bench {
+        year: Option<u16>,
+        day: Option<u8>,
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        runs: u64,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// List every available day
+    List,
+}
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn input(day: &Day, example: bool) -> String {
+    aoc_core::read_path(aoc_core::default_path(root().join(day.path), example))
+        .unwrap_or_else(|err| aoc_core::fail(err))
+}
+
+/// Prints each selected part's answer, returning false if any part failed.
+fn run(day: &Day, part: Option<Part>, example: bool, format: aoc_core::Format) -> bool {
+    let input = input(day, example);
+    let parts: Vec<Part> = day
+        .parts
+        .iter()
+        .copied()
+        .filter(|&p| part.is_none_or(|part| part == p))
+        .collect();
+    if format == aoc_core::Format::Json {
+        return match Report::solve(root().join(day.path), &input, &parts, day.solve) {
+            Ok(report) => {
+                println!("{}", report);
+                true
+            }
+            Err(err) => {
+                eprintln!("error: {} day {:02}: {}", day.year, day.day, err);
+                false
+            }
+        };
+    }
+    let mut ok = true;
+    for &p in &parts {
+        match (day.solve)(&input, p) {
+            Ok(answer) if answer.contains('\n') => {
+                println!("{} day {:02} part {}:\n{}", day.year, day.day, p, answer)
+            }
+            Ok(answer) => println!("{} day {:02} part {}: {}", day.year, day.day, p, answer),
+            Err(err) => {
+                eprintln!("error: {} day {:02} part {}: {}", day.year, day.day, p, err);
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+/// Prints a pass/fail/missing row for every part of every selected day and
+/// input file, returning false on any failure.
+fn verify(days: &[&Day]) -> bool {
+    let mut answers = HashMap::new();
+    let mut counts: HashMap<Status, usize> = HashMap::new();
+    println!(
+        "{:<4}  {:>3}  {:>4}  {:<7}  {:<7}  detail",
+        "year", "day", "part", "input", "status"
+    );
+    for day in days {
+        let answers = answers.entry(day.year).or_insert_with(|| {
+            verify::load(&root().join(day.year.to_string()))
+                .unwrap_or_else(|err| aoc_core::fail(format!("{} answers.toml: {}", day.year, err)))
+        });
+        for example in [false, true] {
+            let path = aoc_core::default_path(root().join(day.path), example);
+            if !path.exists() {
+                continue;
+            }
+            let input = aoc_core::read_path(path).unwrap_or_else(|err| aoc_core::fail(err));
+            for check in verify::check(day, &input, example, answers) {
+                *counts.entry(check.status).or_default() += 1;
+                let row = format!(
+                    "{:<4}  {:>3}  {:>4}  {:<7}  {:<7}  {}",
+                    day.year,
+                    format!("{:02}", day.day),
+                    check.part,
+                    if check.example { "example" } else { "input" },
+                    check.status,
+                    check.detail
+                );
+                println!("{}", row.trim_end());
+            }
+        }
+    }
+    let count = |status| counts.get(&status).copied().unwrap_or(0);
+    println!(
+        "{} passed, {} failed, {} missing",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing)
+    );
+    count(Status::Fail) == 0
+}
+
+/// Benchmarks each selected day on its input, followed by a total per year.
+/// Days whose answers fail are reported and left out.
+fn bench(days: &[&Day], runs: usize, format: Format) -> bool {
+    let mut rows: Vec<Row> = Vec::new();
+    let mut ok = true;
+    for day in days {
+        let input = input(day, false);
+        // Checks every part succeeds before timing it, which also warms up.
+        let checked = day.parts.iter().try_for_each(|&part| {
+            (day.solve)(&input, part)
+                .map(|_| ())
+                .map_err(|err| (part, err))
+        });
+        let samples = match checked {
+            Ok(()) => (day.bench)(&input, day.parts, runs).map_err(|err| (Part::One, err)),
+            Err(err) => Err(err),
+        };
+        match samples {
+            Ok(samples) => rows.extend(bench::rows(day.year, day.day, runs, &samples)),
+            Err((part, err)) => {
+                eprintln!(
+                    "error: {} day {:02} part {}: {}",
+                    day.year, day.day, part, err
+                );
+                ok = false;
+            }
+        }
+    }
+    let mut years: Vec<u16> = rows.iter().map(|row| row.year).collect();
+    years.dedup();
+    for year in years {
+        let total = bench::total(year, runs, &rows);
+        let end = rows.iter().rposition(|row| row.year == year).unwrap() + 1;
+        rows.insert(end, total);
+    }
+    println!("{}", bench::report(&rows, format));
+    ok
+}
+
+fn main() {
+    let Args { command } = Args::parse();
+    match command {
+        Command::Run {
+            year,
+            day,
+            part,
+            all,
+            example,
+            format,
+        } => {
+            let selected: Vec<&Day> = DAYS
+                .iter()
+                .filter(|d| d.year == year && (all || Some(d.day) == day))
+                .collect();
+            if selected.is_empty() {
+                match day {
+                    Some(day) => eprintln!("no solution for {} day {}", year, day),
+                    None => eprintln!("no solutions for {}", year),
+                }
+                process::exit(1);
+            }
+            // A named day must have the part asked for; with --all, days
+            // without it are just skipped.
+            if let (Some(part), false) = (part, all) {
+                if let Some(day) = selected.iter().find(|day| !day.parts.contains(&part)) {
+                    eprintln!(
+                        "error: {} day {:02} has no part {}",
+                        day.year, day.day, part
+                    );
+                    process::exit(1);
+                }
+            }
+            let failed = selected
+                .into_iter()
+                .filter(|day| !run(day, part, example, format))
+                .count();
+            if failed > 0 {
+                process::exit(1);
+            }
+        }
+        Command::Verify { year, day } => {
+            let selected: Vec<&Day> = DAYS
+                .iter()
+                .filter(|d| year.is_none_or(|year| d.year == year))
+                .filter(|d| day.is_none_or(|day| d.day == day))
+                .collect();
+            if selected.is_empty() {
+                eprintln!("no solutions to verify");
+                process::exit(1);
+            }
+            if !verify(&selected) {
+                process::exit(1);
+            }
+        }
+        Command::Bench {
+            year,
+            day,
+            runs,
+            format,
+        } => {
+            let selected: Vec<&Day> = DAYS
+                .iter()
+                .filter(|d| year.is_none_or(|year| d.year == year))
+                .filter(|d| day.is_none_or(|day| d.day == day))
+                .collect();
+            if selected.is_empty() {
+                eprintln!("no solutions to benchmark");
+                process::exit(1);
+            }
+            if !bench(&selected, runs as usize, format) {
+                process::exit(1);
+            }
+        }
+        Command::List => DAYS.iter().for_each(|day| {
+            println!(
+                "{} {:02} {} (parts: {})",
+                day.year,
+                day.day,
+                day.name(),
+                day.parts
+                    .iter()
+                    .map(Part::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+        }),
+    }
+}
@@ -0,0 +1,163 @@
+use crate::days::Day;
+use aoc_core::Part;
+use serde::Deserialize;
+use std::{collections::HashMap, fmt, fs, io, path::Path};
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum Answer {
+    Text(String),
+    Number(i64),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+struct Answers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+impl Answers {
+    fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    #[serde(default)]
+    input: Answers,
+    #[serde(default)]
+    example: Answers,
+}
+
+/// The recorded answers for one year, keyed by two-digit day.
+#[derive(Deserialize, Default, Debug)]
+#[serde(transparent)]
+pub struct YearAnswers(HashMap<String, DayAnswers>);
+
+impl YearAnswers {
+    fn get(&self, day: u8, example: bool, part: Part) -> Option<&Answer> {
+        self.0
+            .get(&format!("{:02}", day))
+            .and_then(|answers| match example {
+                true => answers.example.get(part),
+                false => answers.input.get(part),
+            })
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "{}", err),
+            LoadError::Toml(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Reads `answers.toml` from a year's directory; a missing file records nothing.
+pub fn load(dir: &Path) -> Result<YearAnswers, LoadError> {
+    match fs::read_to_string(dir.join("answers.toml")) {
+        Ok(text) => toml::from_str(&text).map_err(LoadError::Toml),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(YearAnswers::default()),
+        Err(err) => Err(LoadError::Io(err)),
+    }
+}
+
+#[derive(Copy, Clone, Eq, Hash, PartialEq, Debug)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        })
+    }
+}
+
+pub struct Check {
+    pub part: Part,
+    pub example: bool,
+    pub status: Status,
+    pub detail: String,
+}
+
+fn one_line(answer: &str) -> String {
+    answer.lines().collect::<Vec<&str>>().join(" / ")
+}
+
+/// Runs every part of `day` against `input` and compares with `answers`.
+pub fn check(day: &Day, input: &str, example: bool, answers: &YearAnswers) -> Vec<Check> {
+    day.parts
+        .iter()
+        .map(|&part| {
+            let expected = answers.get(day.day, example, part).map(Answer::to_string);
+            let (status, detail) = match ((day.solve)(input, part), expected) {
+                (Err(err), _) => (Status::Fail, err.to_string()),
+                (Ok(answer), None) => (Status::Missing, format!("got {}", one_line(&answer))),
+                (Ok(answer), Some(expected)) if answer.trim_end() == expected.trim_end() => {
+                    (Status::Pass, String::new())
+                }
+                (Ok(answer), Some(expected)) => (
+                    Status::Fail,
+                    format!(
+                        "expected {}, got {}",
+                        one_line(&expected),
+                        one_line(&answer)
+                    ),
+                ),
+            };
+            Check {
+                part,
+                example,
+                status,
+                detail,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::verify::YearAnswers;
+    use aoc_core::Part;
+
+    #[test]
+    fn reads_numbers_and_text() {
+        let answers: YearAnswers = toml::from_str(
+            "[\"05\".input]\npart1 = \"CMZ\"\npart2 = 12\n\n[\"05\".example]\npart2 = \"MCD\"\n",
+        )
+        .unwrap();
+        let get = |example, part| answers.get(5, example, part).map(|a| a.to_string());
+        assert_eq!(get(false, Part::One).as_deref(), Some("CMZ"));
+        assert_eq!(get(false, Part::Two).as_deref(), Some("12"));
+        assert_eq!(get(true, Part::One), None);
+        assert_eq!(get(true, Part::Two).as_deref(), Some("MCD"));
+    }
+}